@@ -0,0 +1,4 @@
+-- Links paired ledger entries, e.g. both sides of a transfer
+ALTER TABLE currency_transactions ADD COLUMN counterpart_id INTEGER REFERENCES currency_transactions(id);
+
+CREATE INDEX IF NOT EXISTS idx_currency_transactions_user_kind ON currency_transactions(user_id, kind);
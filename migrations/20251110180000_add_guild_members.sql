@@ -0,0 +1,12 @@
+-- Records which guilds a user has been seen in, used by per-guild rankings
+CREATE TABLE IF NOT EXISTS guild_members (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    user_id INTEGER NOT NULL,
+    guild_id INTEGER NOT NULL,
+    first_seen_at TEXT NOT NULL,
+    last_seen_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE(user_id, guild_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_guild_members_guild_id ON guild_members(guild_id);
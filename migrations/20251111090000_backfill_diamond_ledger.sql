@@ -0,0 +1,15 @@
+-- Diamonds were credited without ledger entries; record whatever is not yet accounted for
+-- as an opening balance so each user's diamond history sums to their stored balance
+INSERT INTO currency_transactions (user_id, amount, balance_after, currency, kind, context, created_at)
+SELECT
+    u.id,
+    u.diamonds - COALESCE(SUM(t.amount), 0),
+    u.diamonds - COALESCE(SUM(t.amount), 0),
+    'diamonds',
+    'opening_balance',
+    'Saldo anterior ao histórico de diamantes',
+    u.created_at
+FROM users u
+LEFT JOIN currency_transactions t ON t.user_id = u.id AND t.currency = 'diamonds'
+GROUP BY u.id
+HAVING u.diamonds - COALESCE(SUM(t.amount), 0) != 0;
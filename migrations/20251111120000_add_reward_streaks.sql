@@ -0,0 +1,4 @@
+-- Consecutive claims per reward type; existing claimers start a fresh streak
+ALTER TABLE reward_states ADD COLUMN streak INTEGER NOT NULL DEFAULT 0;
+
+UPDATE reward_states SET streak = 1 WHERE total_claims > 0;
@@ -0,0 +1,5 @@
+-- Refunds append a compensating entry pointing at the original instead of deleting it
+ALTER TABLE currency_transactions ADD COLUMN reversal_of INTEGER REFERENCES currency_transactions(id);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_currency_transactions_reversal_of
+    ON currency_transactions(reversal_of) WHERE reversal_of IS NOT NULL;
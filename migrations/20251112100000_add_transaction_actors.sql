@@ -0,0 +1,4 @@
+-- Discord ID of the moderator behind manual adjustments
+ALTER TABLE currency_transactions ADD COLUMN actor_id INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_currency_transactions_kind ON currency_transactions(kind);
@@ -0,0 +1,35 @@
+-- Items that can be bought with dollars or diamonds
+CREATE TABLE IF NOT EXISTS shop_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    slug TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    price INTEGER NOT NULL CHECK (price > 0),
+    currency TEXT NOT NULL CHECK (currency IN ('dollars', 'diamonds')),
+    kind TEXT NOT NULL CHECK (kind IN ('consumable', 'cosmetic')),
+    active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- What each user owns; cosmetics never go above one unit
+CREATE TABLE IF NOT EXISTS inventory (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    user_id INTEGER NOT NULL,
+    item_id INTEGER NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 0 CHECK (quantity >= 0),
+    acquired_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (item_id) REFERENCES shop_items(id) ON DELETE CASCADE,
+    UNIQUE(user_id, item_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_inventory_user_id ON inventory(user_id);
+
+INSERT OR IGNORE INTO shop_items (slug, name, description, price, currency, kind) VALUES
+    ('biscoito_sorte', 'Biscoito da sorte', 'Um biscoito crocante com uma mensagem misteriosa.', 150, 'dollars', 'consumable'),
+    ('bilhete_rifa', 'Bilhete de rifa', 'Vale uma participação em sorteios da comunidade.', 500, 'dollars', 'consumable'),
+    ('cha_lunar', 'Chá lunar', 'Uma xícara quentinha para depois de uma rodada difícil.', 2, 'diamonds', 'consumable'),
+    ('titulo_apostador', 'Título: Apostador', 'Mostra a todos que você não tem medo de bombas.', 20000, 'dollars', 'cosmetic'),
+    ('moldura_lua', 'Moldura lunar', 'Uma moldura prateada para o seu perfil.', 25, 'diamonds', 'cosmetic'),
+    ('coroa_fumo', 'Coroa Fumo', 'A peça mais rara da loja.', 100, 'diamonds', 'cosmetic');
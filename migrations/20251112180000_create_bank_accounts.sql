@@ -0,0 +1,9 @@
+-- Savings kept apart from users.dollars; last_interest_at is the last daily reset already paid
+CREATE TABLE IF NOT EXISTS bank_accounts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    user_id INTEGER NOT NULL UNIQUE,
+    balance INTEGER NOT NULL DEFAULT 0 CHECK (balance >= 0),
+    last_interest_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,18 @@
+-- Dollar loans; amount_due already includes the flat interest
+CREATE TABLE IF NOT EXISTS loans (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    user_id INTEGER NOT NULL,
+    principal INTEGER NOT NULL CHECK (principal > 0),
+    interest INTEGER NOT NULL CHECK (interest >= 0),
+    amount_due INTEGER NOT NULL,
+    amount_repaid INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'repaid', 'defaulted')),
+    created_at TEXT NOT NULL,
+    due_at TEXT NOT NULL,
+    repaid_at TEXT,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- At most one unpaid loan per user
+CREATE UNIQUE INDEX IF NOT EXISTS idx_loans_open_user
+    ON loans(user_id) WHERE status IN ('active', 'defaulted');
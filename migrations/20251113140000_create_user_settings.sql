@@ -0,0 +1,7 @@
+-- Per-user preferences; a missing row means every setting is at its default
+CREATE TABLE IF NOT EXISTS user_settings (
+    user_id INTEGER PRIMARY KEY NOT NULL,
+    hide_balance INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,12 @@
+-- Message XP per guild; level is the last level already rewarded
+CREATE TABLE IF NOT EXISTS member_levels (
+    guild_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    xp INTEGER NOT NULL DEFAULT 0,
+    level INTEGER NOT NULL DEFAULT 0,
+    last_message_at TEXT NOT NULL,
+    PRIMARY KEY (guild_id, user_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_member_levels_guild_xp ON member_levels(guild_id, xp DESC);
@@ -0,0 +1,14 @@
+-- Self-imposed gambling limits. NULL limits mean "no limit"; a pending change is
+-- identified by its *_at column and only replaces the limit once that time passes
+CREATE TABLE IF NOT EXISTS gambling_limits (
+    user_id INTEGER PRIMARY KEY NOT NULL,
+    daily_limit INTEGER CHECK (daily_limit IS NULL OR daily_limit >= 0),
+    weekly_limit INTEGER CHECK (weekly_limit IS NULL OR weekly_limit >= 0),
+    pending_daily_limit INTEGER,
+    pending_daily_at TEXT,
+    pending_weekly_limit INTEGER,
+    pending_weekly_at TEXT,
+    excluded_until TEXT,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
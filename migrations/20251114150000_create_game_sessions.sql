@@ -0,0 +1,20 @@
+-- In-flight wagered games, so a restart can settle them. Boards are stored as one
+-- character per tile: layout marks bombs with '1', revealed marks opened tiles with '1'
+CREATE TABLE IF NOT EXISTS game_sessions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    game TEXT NOT NULL,
+    user_id INTEGER NOT NULL,
+    channel_id INTEGER,
+    message_id INTEGER,
+    wager INTEGER NOT NULL,
+    wager_transaction_id INTEGER NOT NULL,
+    layout TEXT NOT NULL,
+    revealed TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (wager_transaction_id) REFERENCES currency_transactions(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_game_sessions_active ON game_sessions(game) WHERE status = 'active';
@@ -0,0 +1,101 @@
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{self, UserAchievementModel},
+    functions::{
+        achievements::{ACHIEVEMENTS, Achievement, format_reward},
+        format::{
+            discord::{bold, italic},
+            pretty_message,
+        },
+        interactions::pagination::paginate,
+        time,
+    },
+};
+use poise::serenity_prelude as serenity;
+use serenity::builder::CreateEmbedFooter;
+use std::time::Duration;
+
+const ACHIEVEMENTS_PAGE_SIZE: usize = 5;
+const ACHIEVEMENTS_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Veja as conquistas desbloqueadas.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "conquistas",
+    aliases("badges"),
+    category = "Economia",
+    interaction_context = "Guild",
+    on_error = "crate::commands::util::command_error_handler"
+)]
+pub async fn achievements(
+    ctx: Context<'_>,
+    #[description = "Ver as conquistas de outra pessoa"] usuario: Option<serenity::User>,
+) -> Result<(), Error> {
+    let target = usuario.as_ref().unwrap_or_else(|| ctx.author());
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, target.id.get() as i64).await?;
+    let unlocked = database::achievement::list_by_user(&db, user.id).await?;
+
+    let pages: Vec<String> = ACHIEVEMENTS
+        .chunks(ACHIEVEMENTS_PAGE_SIZE)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|achievement| format_achievement(achievement, &unlocked))
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        })
+        .collect();
+    let owner_name = target.name.clone();
+    let unlocked_count = unlocked.len();
+
+    paginate(
+        ctx,
+        pages.len(),
+        ACHIEVEMENTS_TIMEOUT,
+        false,
+        0,
+        move |current_page, total_pages| {
+            let embed = serenity::CreateEmbed::new()
+                .title(format!("{} Conquistas de {}", icon::GIFT, owner_name))
+                .colour(colors::MOON)
+                .description(pages[current_page].clone())
+                .footer(CreateEmbedFooter::new(format!(
+                    "Página {}/{} • {}/{} desbloqueadas",
+                    current_page + 1,
+                    total_pages,
+                    unlocked_count,
+                    ACHIEVEMENTS.len()
+                )));
+            (embed, Vec::new())
+        },
+    )
+    .await
+}
+
+fn format_achievement(achievement: &Achievement, unlocked: &[UserAchievementModel]) -> String {
+    let entry = unlocked.iter().find(|entry| entry.slug == achievement.slug);
+    let badge = if entry.is_some() {
+        achievement.badge
+    } else {
+        "🔒"
+    };
+    let reward = achievement
+        .reward
+        .map(|(currency, amount)| format!(" • {}", format_reward(currency, amount)))
+        .unwrap_or_default();
+    let status = match entry {
+        Some(entry) => time::describe_relative_from_str(&entry.unlocked_at)
+            .map(|when| pretty_message(icon::CHECK, format!("Desbloqueada {when}")))
+            .unwrap_or_else(|| pretty_message(icon::CHECK, "Desbloqueada")),
+        None => pretty_message(icon::TIMER, "Bloqueada"),
+    };
+
+    format!(
+        "{badge} {}{reward}\n{}\n{status}",
+        bold(achievement.name),
+        italic(achievement.description)
+    )
+}
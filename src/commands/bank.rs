@@ -0,0 +1,225 @@
+use crate::{
+    Context, Error,
+    commands::economy::RESET_CONFIG,
+    constants::{colors, icon},
+    database::{self, BankAccountModel, bank::BankOutcome},
+    functions::{
+        bot::bank_interest::{INTEREST_TIERS, tier_for},
+        format::{discord::bold, format_currency, pretty_message},
+        time::{self, ResetPeriod},
+    },
+};
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+use serenity::builder::CreateEmbedFooter;
+
+/// Guarde moedas na poupança e receba juros diários.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "banco",
+    category = "Economia",
+    interaction_context = "Guild",
+    on_error = "crate::commands::util::command_error_handler",
+    subcommands("bank_deposit", "bank_withdraw", "bank_balance")
+)]
+pub async fn bank(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Deposite moedas na poupança.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "depositar",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn bank_deposit(
+    ctx: Context<'_>,
+    #[description = "Quantidade de moedas a depositar"] valor: i64,
+) -> Result<(), Error> {
+    if !ensure_positive(&ctx, valor).await? {
+        return Ok(());
+    }
+
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let opened_at = time::last_daily_reset(Utc::now(), &RESET_CONFIG);
+
+    let content = match database::bank::deposit(&db, user.id, valor, opened_at).await? {
+        BankOutcome::Completed { user, account } => format!(
+            "{}\n{}",
+            pretty_message(
+                icon::CHECK,
+                format!("Você depositou {} moedas.", bold(format_currency(valor)))
+            ),
+            balances_line(user.dollars, &account)
+        ),
+        BankOutcome::InsufficientFunds => pretty_message(
+            icon::ERROR,
+            format!(
+                "Saldo insuficiente. Você tem {} moedas na carteira.",
+                bold(format_currency(user.dollars.max(0)))
+            ),
+        ),
+    };
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Saque moedas da poupança.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "sacar",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn bank_withdraw(
+    ctx: Context<'_>,
+    #[description = "Quantidade de moedas a sacar"] valor: i64,
+) -> Result<(), Error> {
+    if !ensure_positive(&ctx, valor).await? {
+        return Ok(());
+    }
+
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let opened_at = time::last_daily_reset(Utc::now(), &RESET_CONFIG);
+
+    let content = match database::bank::withdraw(&db, user.id, valor, opened_at).await? {
+        BankOutcome::Completed { user, account } => format!(
+            "{}\n{}",
+            pretty_message(
+                icon::CHECK,
+                format!("Você sacou {} moedas.", bold(format_currency(valor)))
+            ),
+            balances_line(user.dollars, &account)
+        ),
+        BankOutcome::InsufficientFunds => {
+            let account = database::bank::get_or_open(&db, user.id, opened_at).await?;
+            pretty_message(
+                icon::ERROR,
+                format!(
+                    "Sua poupança tem apenas {} moedas.",
+                    bold(format_currency(account.balance))
+                ),
+            )
+        }
+    };
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Consulte sua poupança e os juros.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "saldo",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn bank_balance(ctx: Context<'_>) -> Result<(), Error> {
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let now = Utc::now();
+    let account =
+        database::bank::get_or_open(&db, user.id, time::last_daily_reset(now, &RESET_CONFIG))
+            .await?;
+
+    let earning_balance = database::bank::earning_balance(
+        &db,
+        user.id,
+        time::last_daily_reset(now, &RESET_CONFIG),
+        now,
+    )
+    .await?;
+    let tier = tier_for(earning_balance);
+    let next_payout = time::next_reset_from(now, ResetPeriod::Daily, &RESET_CONFIG);
+    let tiers_text = INTEREST_TIERS
+        .iter()
+        .rev()
+        .map(|tier| {
+            format!(
+                "{} • a partir de {} • {}% ao dia • até {} por dia",
+                bold(tier.name),
+                format_currency(tier.min_balance),
+                format_rate(tier.rate_bps),
+                format_currency(tier.daily_cap)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("{} Banco de {}", icon::HOUSE, ctx.author().name))
+        .colour(colors::MOON)
+        .description(
+            [
+                balances_line(user.dollars, &account),
+                pretty_message(
+                    icon::PLUS,
+                    format!(
+                        "Faixa {} • próximo rendimento: {} moedas",
+                        bold(tier.name),
+                        bold(format_currency(tier.interest_for(earning_balance)))
+                    ),
+                ),
+                pretty_message(icon::ALARM, time::describe_absolute(next_payout)),
+            ]
+            .join("\n"),
+        )
+        .field("Faixas de juros", tiers_text, false)
+        .footer(CreateEmbedFooter::new(
+            "Os juros são pagos no reset diário sobre o menor saldo da poupança no dia",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+async fn ensure_positive(ctx: &Context<'_>, amount: i64) -> Result<bool, Error> {
+    if amount > 0 {
+        return Ok(true);
+    }
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(pretty_message(icon::ERROR, "O valor precisa ser positivo."))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(false)
+}
+
+fn balances_line(wallet: i64, account: &BankAccountModel) -> String {
+    pretty_message(
+        icon::DOLLAR,
+        format!(
+            "Carteira: {} • Poupança: {}",
+            bold(format_currency(wallet.max(0))),
+            bold(format_currency(account.balance))
+        ),
+    )
+}
+
+fn format_rate(rate_bps: i64) -> String {
+    format!("{},{:02}", rate_bps / 100, rate_bps % 100)
+}
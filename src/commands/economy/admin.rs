@@ -0,0 +1,361 @@
+use super::currency_choice::CurrencyChoice;
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{self, KindEntryModel, WalletChange, wallet::WalletUpdate},
+    functions::{
+        format::{
+            discord::{bold, inline_code, italic, mention},
+            format_currency, pretty_message,
+        },
+        interactions::pagination::paginate,
+        time,
+    },
+};
+use poise::serenity_prelude::{self as serenity, Mentionable};
+use serenity::builder::CreateEmbedFooter;
+use std::time::Duration;
+
+const ADMIN_ADJUST_KIND: &str = "admin_adjust";
+const HISTORY_PAGE_SIZE: usize = 5;
+const HISTORY_FETCH_LIMIT: i64 = 100;
+const HISTORY_TIMEOUT: Duration = Duration::from_secs(180);
+const MAX_REASON_LENGTH: usize = 200;
+
+/// Ferramentas administrativas para corrigir saldos.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "admin",
+    category = "Equipe",
+    owners_only,
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true,
+    subcommands("admin_grant", "admin_revoke", "admin_set", "admin_history")
+)]
+pub async fn admin(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Adiciona moedas ou diamantes ao saldo de um usuário.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "conceder",
+    category = "Equipe",
+    owners_only,
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn admin_grant(
+    ctx: Context<'_>,
+    #[description = "Usuário que receberá o valor"] usuario: serenity::User,
+    #[description = "Moeda ajustada"] moeda: CurrencyChoice,
+    #[description = "Quantidade a adicionar"] valor: i64,
+    #[description = "Motivo do ajuste"] motivo: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+    let Some(reason) = validate_request(&ctx, Some(valor), &motivo).await? else {
+        return Ok(());
+    };
+
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, usuario.id.get() as i64).await?;
+    let change = adjustment(&ctx, moeda, valor, reason.clone());
+    let update = database::wallet::apply(&db, user.id, change).await?;
+
+    respond(&ctx, &usuario, moeda, &reason, update).await
+}
+
+/// Remove moedas ou diamantes do saldo de um usuário.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "revogar",
+    category = "Equipe",
+    owners_only,
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn admin_revoke(
+    ctx: Context<'_>,
+    #[description = "Usuário que perderá o valor"] usuario: serenity::User,
+    #[description = "Moeda ajustada"] moeda: CurrencyChoice,
+    #[description = "Quantidade a remover"] valor: i64,
+    #[description = "Motivo do ajuste"] motivo: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+    let Some(reason) = validate_request(&ctx, Some(valor), &motivo).await? else {
+        return Ok(());
+    };
+
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, usuario.id.get() as i64).await?;
+    let change = adjustment(&ctx, moeda, -valor, reason.clone());
+    let Some(update) = database::wallet::apply(&db, user.id, change).await? else {
+        ctx.send(poise::CreateReply::default().content(pretty_message(
+            icon::ERROR,
+            format!(
+                "{} possui apenas {} {}. Use {} para zerar o saldo.",
+                usuario.mention(),
+                bold(format_currency(moeda.currency().balance_of(&user))),
+                moeda.label(),
+                inline_code("/economia admin definir")
+            ),
+        )))
+        .await?;
+        return Ok(());
+    };
+
+    respond(&ctx, &usuario, moeda, &reason, Some(update)).await
+}
+
+/// Define o saldo exato de um usuário.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "definir",
+    category = "Equipe",
+    owners_only,
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn admin_set(
+    ctx: Context<'_>,
+    #[description = "Usuário ajustado"] usuario: serenity::User,
+    #[description = "Moeda ajustada"] moeda: CurrencyChoice,
+    #[description = "Novo saldo"]
+    #[min = 0]
+    valor: i64,
+    #[description = "Motivo do ajuste"] motivo: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+    let Some(reason) = validate_request(&ctx, None, &motivo).await? else {
+        return Ok(());
+    };
+
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, usuario.id.get() as i64).await?;
+    let change = adjustment(&ctx, moeda, 0, reason.clone());
+    let Some(update) = database::wallet::set_balance(&db, user.id, valor, change).await? else {
+        ctx.send(poise::CreateReply::default().content(pretty_message(
+            icon::MINUS,
+            format!(
+                "O saldo de {} já é {} {}. Nada foi alterado.",
+                usuario.mention(),
+                bold(format_currency(valor)),
+                moeda.label()
+            ),
+        )))
+        .await?;
+        return Ok(());
+    };
+
+    respond(&ctx, &usuario, moeda, &reason, Some(update)).await
+}
+
+/// Lista os ajustes administrativos mais recentes.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "histórico",
+    category = "Equipe",
+    owners_only,
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn admin_history(
+    ctx: Context<'_>,
+    #[description = "Mostrar apenas ajustes deste usuário"] usuario: Option<serenity::User>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let db = ctx.data().database.clone();
+    let user_id = match &usuario {
+        Some(target) => Some(
+            database::get_or_create_user(&db, target.id.get() as i64)
+                .await?
+                .id,
+        ),
+        None => None,
+    };
+    let entries = database::transaction::list_recent_by_kind(
+        &db,
+        ADMIN_ADJUST_KIND,
+        user_id,
+        HISTORY_FETCH_LIMIT,
+    )
+    .await?;
+
+    if entries.is_empty() {
+        ctx.send(poise::CreateReply::default().content(pretty_message(
+            icon::MINUS,
+            "Nenhum ajuste administrativo registrado.",
+        )))
+        .await?;
+        return Ok(());
+    }
+
+    let pages: Vec<String> = entries
+        .chunks(HISTORY_PAGE_SIZE)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(format_history_entry)
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        })
+        .collect();
+    let total_entries = entries.len();
+
+    paginate(
+        ctx,
+        pages.len(),
+        HISTORY_TIMEOUT,
+        true,
+        0,
+        move |current_page, total_pages| {
+            let embed = serenity::CreateEmbed::new()
+                .title(format!("{} Ajustes administrativos", icon::HAMMER))
+                .colour(colors::MOON)
+                .description(pages[current_page].clone())
+                .footer(CreateEmbedFooter::new(format!(
+                    "Página {}/{} • {} ajustes (máx. {})",
+                    current_page + 1,
+                    total_pages,
+                    total_entries,
+                    HISTORY_FETCH_LIMIT
+                )));
+            (embed, Vec::new())
+        },
+    )
+    .await
+}
+
+/// Checks the reason (and the delta, for grants and revokes) shared by every adjustment.
+/// Replies and returns `None` when invalid
+async fn validate_request(
+    ctx: &Context<'_>,
+    amount: Option<i64>,
+    reason: &str,
+) -> Result<Option<String>, Error> {
+    let reason = reason.trim();
+    let problem = if amount.is_some_and(|amount| amount <= 0) {
+        Some("O valor precisa ser positivo.")
+    } else if reason.is_empty() {
+        Some("Informe um motivo para o ajuste.")
+    } else if reason.chars().count() > MAX_REASON_LENGTH {
+        Some("O motivo pode ter no máximo 200 caracteres.")
+    } else {
+        None
+    };
+
+    if let Some(problem) = problem {
+        ctx.send(poise::CreateReply::default().content(pretty_message(icon::ERROR, problem)))
+            .await?;
+        return Ok(None);
+    }
+
+    Ok(Some(reason.to_string()))
+}
+
+fn adjustment(
+    ctx: &Context<'_>,
+    choice: CurrencyChoice,
+    amount: i64,
+    reason: String,
+) -> WalletChange {
+    WalletChange::new(choice.currency(), amount, ADMIN_ADJUST_KIND)
+        .with_context(reason)
+        .with_actor(ctx.author().id.get() as i64)
+}
+
+async fn respond(
+    ctx: &Context<'_>,
+    target: &serenity::User,
+    choice: CurrencyChoice,
+    reason: &str,
+    update: Option<WalletUpdate>,
+) -> Result<(), Error> {
+    let Some(update) = update else {
+        ctx.send(poise::CreateReply::default().content(pretty_message(
+            icon::ERROR,
+            "Não foi possível aplicar o ajuste.",
+        )))
+        .await?;
+        return Ok(());
+    };
+
+    let amount = update.transaction.amount;
+    let sign = if amount >= 0 { "+" } else { "-" };
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("{} Ajuste registrado", icon::CHECK))
+        .colour(colors::MINT)
+        .description(
+            [
+                format!("Usuário: {}", target.mention()),
+                format!(
+                    "Ajuste: {}",
+                    bold(format!(
+                        "{sign}{} {}",
+                        format_currency(amount.abs()),
+                        choice.label()
+                    ))
+                ),
+                format!(
+                    "Novo saldo: {}",
+                    bold(format!(
+                        "{} {}",
+                        format_currency(update.transaction.balance_after),
+                        choice.label()
+                    ))
+                ),
+                format!("Motivo: {}", italic(reason)),
+            ]
+            .join("\n"),
+        )
+        .footer(CreateEmbedFooter::new(format!(
+            "Transação #{}",
+            update.transaction.id
+        )));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+fn format_history_entry(entry: &KindEntryModel) -> String {
+    let sign = if entry.amount >= 0 { "+" } else { "-" };
+    let currency = match entry.currency.as_str() {
+        "dollars" => "moedas",
+        "diamonds" => "diamantes",
+        other => other,
+    };
+    let moderator = entry
+        .actor_id
+        .map(mention)
+        .unwrap_or_else(|| "desconhecido".to_string());
+    let when = time::describe_relative_from_str(&entry.created_at)
+        .unwrap_or_else(|| "momento desconhecido".to_string());
+
+    let mut lines = vec![
+        format!(
+            "{} {} → {}",
+            bold(format!("#{}", entry.id)),
+            bold(format!(
+                "{sign}{} {currency}",
+                format_currency(entry.amount.abs())
+            )),
+            mention(entry.discord_id)
+        ),
+        format!(
+            "Saldo após: {} • Por: {} • {}",
+            format_currency(entry.balance_after),
+            moderator,
+            when
+        ),
+    ];
+    if let Some(reason) = &entry.context {
+        lines.push(format!("Motivo: {}", italic(reason)));
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,154 @@
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{self, BalanceDriftModel, Currency, LedgerChainBreakModel},
+    functions::{
+        format::{
+            discord::{bold, mention},
+            format_currency, pretty_message,
+        },
+        interactions::prompt::{
+            ConfirmationOutcome, ConfirmationPromptOptions, confirmation_prompt,
+        },
+    },
+};
+use poise::serenity_prelude as serenity;
+use std::time::Duration;
+
+const CHAIN_BREAK_LIMIT: i64 = 200;
+const MAX_LISTED_ITEMS: usize = 10;
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Confere se o histórico de transações bate com os saldos salvos.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "auditoria",
+    category = "Equipe",
+    owners_only,
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn audit(
+    ctx: Context<'_>,
+    #[description = "Auditar apenas este usuário"] usuario: Option<serenity::User>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let db = ctx.data().database.clone();
+    let user_id = match &usuario {
+        Some(target) => Some(
+            database::get_or_create_user(&db, target.id.get() as i64)
+                .await?
+                .id,
+        ),
+        None => None,
+    };
+
+    let breaks = database::audit::find_chain_breaks(&db, user_id, CHAIN_BREAK_LIMIT).await?;
+    let drifts = database::audit::find_balance_drifts(&db, user_id).await?;
+
+    if breaks.is_empty() && drifts.is_empty() {
+        ctx.send(poise::CreateReply::default().content(pretty_message(
+            icon::CHECK,
+            "Nenhuma divergência encontrada. O histórico bate com os saldos.",
+        )))
+        .await?;
+        return Ok(());
+    }
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("{} Auditoria da economia", icon::HAMMER))
+        .colour(colors::MOON)
+        .field(
+            format!("Saldos divergentes ({})", drifts.len()),
+            list_or_empty(drifts.iter().map(format_drift).collect()),
+            false,
+        )
+        .field(
+            format!("Quebras no histórico ({})", breaks.len()),
+            list_or_empty(breaks.iter().map(format_chain_break).collect()),
+            false,
+        );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    if drifts.is_empty() {
+        return Ok(());
+    }
+
+    let mut prompt = ConfirmationPromptOptions::new(pretty_message(
+        icon::BELL,
+        format!(
+            "Inserir {} ajuste(s) de reconciliação para alinhar o histórico aos saldos salvos?",
+            bold(drifts.len().to_string())
+        ),
+    ));
+    prompt.timeout = CONFIRMATION_TIMEOUT;
+
+    let confirmation = confirmation_prompt(&ctx, ctx.author().id, prompt).await?;
+    if !matches!(confirmation.outcome, ConfirmationOutcome::Accepted) {
+        ctx.send(poise::CreateReply::default().content(pretty_message(
+            icon::MINUS,
+            "Reparo cancelado. Nada foi alterado.",
+        )))
+        .await?;
+        return Ok(());
+    }
+
+    let context = format!("audit:{}", ctx.author().id.get());
+    let mut repaired = 0;
+    for drift in &drifts {
+        let Some(currency) = Currency::from_db_name(&drift.currency) else {
+            continue;
+        };
+        if database::audit::reconcile(&db, drift.user_id, currency, context.clone())
+            .await?
+            .is_some()
+        {
+            repaired += 1;
+        }
+    }
+
+    ctx.send(poise::CreateReply::default().content(pretty_message(
+        icon::CHECK,
+        format!("{repaired} ajuste(s) de reconciliação registrados."),
+    )))
+    .await?;
+
+    Ok(())
+}
+
+fn format_drift(drift: &BalanceDriftModel) -> String {
+    format!(
+        "{} • {}: saldo {} / histórico {}",
+        mention(drift.discord_id),
+        drift.currency,
+        format_currency(drift.stored_balance),
+        format_currency(drift.ledger_balance)
+    )
+}
+
+fn format_chain_break(entry: &LedgerChainBreakModel) -> String {
+    format!(
+        "#{} {} • {}: {} {:+} ≠ {}",
+        entry.id,
+        mention(entry.discord_id),
+        entry.currency,
+        format_currency(entry.previous_balance),
+        entry.amount,
+        format_currency(entry.balance_after)
+    )
+}
+
+fn list_or_empty(lines: Vec<String>) -> String {
+    if lines.is_empty() {
+        return "Nenhuma".to_string();
+    }
+
+    let hidden = lines.len().saturating_sub(MAX_LISTED_ITEMS);
+    let mut shown: Vec<String> = lines.into_iter().take(MAX_LISTED_ITEMS).collect();
+    if hidden > 0 {
+        shown.push(format!("… e mais {hidden}"));
+    }
+    shown.join("\n")
+}
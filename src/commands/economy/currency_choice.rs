@@ -0,0 +1,33 @@
+use crate::constants::{CustomEmoji, icon};
+use crate::database::Currency;
+
+#[derive(Clone, Copy, poise::ChoiceParameter)]
+pub enum CurrencyChoice {
+    #[name = "Moedas"]
+    Dollars,
+    #[name = "Diamantes"]
+    Diamonds,
+}
+
+impl CurrencyChoice {
+    pub fn currency(self) -> Currency {
+        match self {
+            Self::Dollars => Currency::Dollars,
+            Self::Diamonds => Currency::Diamonds,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Dollars => "moedas",
+            Self::Diamonds => "diamantes",
+        }
+    }
+
+    pub fn icon(self) -> CustomEmoji {
+        match self {
+            Self::Dollars => icon::DOLLAR,
+            Self::Diamonds => icon::DIAMOND,
+        }
+    }
+}
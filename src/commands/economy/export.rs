@@ -0,0 +1,224 @@
+use super::transactions::build_filter;
+use crate::{
+    Context, Error,
+    constants::icon,
+    database::{self, CurrencyTransactionModel},
+    functions::format::{format_currency, pretty_message},
+};
+use poise::serenity_prelude as serenity;
+
+const EXPORT_BATCH_SIZE: i64 = 500;
+/// Most entries a single file may hold; larger histories keep only their newest entries
+const EXPORT_MAX_ROWS: i64 = 20_000;
+const CSV_HEADER: &str =
+    "id,created_at,currency,kind,amount,balance_after,context,counterpart_id,reversal_of";
+
+#[derive(Clone, Copy, poise::ChoiceParameter)]
+pub enum ExportFormat {
+    #[name = "CSV"]
+    Csv,
+    #[name = "JSON"]
+    Json,
+}
+
+impl ExportFormat {
+    fn file_name(self) -> &'static str {
+        match self {
+            Self::Csv => "transacoes.csv",
+            Self::Json => "transacoes.json",
+        }
+    }
+}
+
+/// Exporte todo o seu histórico de transações.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "exportar",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn export(
+    ctx: Context<'_>,
+    #[description = "Formato do arquivo"] formato: ExportFormat,
+    #[description = "Apenas transações deste tipo (ex.: mines_wager)"] tipo: Option<String>,
+    #[description = "Data inicial (DD/MM/AAAA)"] desde: Option<String>,
+    #[description = "Data final, inclusiva (DD/MM/AAAA)"] ate: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let filter = match build_filter(tipo, desde.as_deref(), ate.as_deref()) {
+        Ok(filter) => filter,
+        Err(message) => {
+            ctx.send(poise::CreateReply::default().content(pretty_message(icon::ERROR, message)))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+
+    // Pages come newest first; each one is written out as it arrives, oldest row first,
+    // and the pages are stitched back in reverse so the file reads forward like a statement
+    let mut pages = Vec::new();
+    let mut exported = 0i64;
+    let mut truncated = false;
+    let mut before_id = None;
+
+    loop {
+        // One row past the cap tells a history that is too big apart from one that fits exactly
+        let limit = EXPORT_BATCH_SIZE.min(EXPORT_MAX_ROWS + 1 - exported);
+        let mut batch =
+            database::transaction::list_before(&db, user.id, &filter, before_id, limit).await?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        before_id = Some(last.id);
+        let full_page = batch.len() as i64 == limit;
+
+        if exported + batch.len() as i64 > EXPORT_MAX_ROWS {
+            batch.truncate((EXPORT_MAX_ROWS - exported) as usize);
+            truncated = true;
+        }
+
+        let mut page = String::new();
+        for entry in batch.iter().rev() {
+            match formato {
+                ExportFormat::Csv => push_csv_row(&mut page, entry),
+                ExportFormat::Json => {
+                    if !page.is_empty() {
+                        page.push(',');
+                    }
+                    push_json_object(&mut page, entry);
+                }
+            }
+        }
+        exported += batch.len() as i64;
+        if !page.is_empty() {
+            pages.push(page);
+        }
+
+        if truncated || !full_page {
+            break;
+        }
+    }
+
+    if exported == 0 {
+        ctx.send(poise::CreateReply::default().content(pretty_message(
+            icon::EMPTY,
+            "Nenhuma transação encontrada com esses filtros.",
+        )))
+        .await?;
+        return Ok(());
+    }
+
+    pages.reverse();
+    let output = match formato {
+        ExportFormat::Csv => format!("{CSV_HEADER}\n{}", pages.concat()),
+        ExportFormat::Json => format!("[{}]", pages.join(",")),
+    };
+
+    let mut summary = vec![pretty_message(
+        icon::CHECK,
+        format!("{} transações exportadas.", format_currency(exported)),
+    )];
+    if truncated {
+        summary.push(pretty_message(
+            icon::BELL,
+            format!(
+                "O histórico passa de {} transações, então o arquivo traz só as mais recentes. \
+                Use o filtro de data final para exportar as anteriores.",
+                format_currency(EXPORT_MAX_ROWS)
+            ),
+        ));
+    }
+
+    let attachment = serenity::CreateAttachment::bytes(output.into_bytes(), formato.file_name());
+    ctx.send(
+        poise::CreateReply::default()
+            .content(summary.join("\n"))
+            .attachment(attachment),
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn push_csv_row(output: &mut String, entry: &CurrencyTransactionModel) {
+    let fields = [
+        entry.id.to_string(),
+        csv_field(&entry.created_at),
+        csv_field(&entry.currency),
+        csv_field(&entry.kind),
+        entry.amount.to_string(),
+        entry.balance_after.to_string(),
+        csv_field(entry.context.as_deref().unwrap_or_default()),
+        entry
+            .counterpart_id
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        entry
+            .reversal_of
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+    ];
+    output.push_str(&fields.join(","));
+    output.push('\n');
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn push_json_object(output: &mut String, entry: &CurrencyTransactionModel) {
+    let context = entry
+        .context
+        .as_deref()
+        .map(json_string)
+        .unwrap_or_else(|| "null".to_string());
+    let counterpart = optional_id(entry.counterpart_id);
+    let reversal_of = optional_id(entry.reversal_of);
+
+    output.push_str(&format!(
+        "{{\"id\":{},\"created_at\":{},\"currency\":{},\"kind\":{},\"amount\":{},\
+        \"balance_after\":{},\"context\":{},\"counterpart_id\":{},\"reversal_of\":{}}}",
+        entry.id,
+        json_string(&entry.created_at),
+        json_string(&entry.currency),
+        json_string(&entry.kind),
+        entry.amount,
+        entry.balance_after,
+        context,
+        counterpart,
+        reversal_of,
+    ));
+}
+
+fn optional_id(id: Option<i32>) -> String {
+    id.map(|id| id.to_string())
+        .unwrap_or_else(|| "null".to_string())
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
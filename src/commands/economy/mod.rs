@@ -1,35 +1,45 @@
 use crate::{
     Context, Error,
     constants::{colors, icon},
-    database::{self, CurrencyTransactionModel, RewardStateModel, UserModel},
+    database::{self, Currency, RewardStateModel, UserModel, WalletChange, reward::RewardClaim},
     functions::{
-        format::{
-            discord::{bold, inline_code, italic},
-            format_currency, pretty_message,
-        },
-        interactions::pagination::paginate,
+        achievements::{self, AchievementEvent},
+        format::pretty_message,
         time::{self, ResetTime},
     },
 };
 use chrono::{DateTime, Utc};
 use poise::serenity_prelude as serenity;
 use rand::Rng;
-use serenity::builder::{CreateEmbedFooter, CreateInteractionResponseMessage};
+use serenity::builder::CreateInteractionResponseMessage;
 use serenity::collector::ComponentInteractionCollector;
 use serenity::{CreateActionRow, CreateButton};
 use std::time::Duration;
 
+mod admin;
+mod audit;
+mod currency_choice;
+mod export;
+mod ranking;
 mod reward_kind;
+mod stats;
+mod streak;
+mod transactions;
+mod transfer;
+use admin::admin;
+use audit::audit;
+use export::export;
+use ranking::ranking;
 use reward_kind::RewardKind;
+use stats::stats;
+use transactions::transactions;
+use transfer::transfer;
 
-const RESET_CONFIG: ResetTime = ResetTime {
+pub const RESET_CONFIG: ResetTime = ResetTime {
     hour: 21,
     minute: 0,
     timezone_offset_secs: -3 * 60 * 60,
 };
-const TRANSACTION_FETCH_LIMIT: i64 = 50;
-const TRANSACTION_PAGE_SIZE: usize = 5;
-const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(180);
 
 /// Gerencie sua economia e recompensas.
 #[poise::command(
@@ -39,44 +49,21 @@ const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(180);
     category = "Economia",
     interaction_context = "Guild",
     on_error = "crate::commands::util::command_error_handler",
-    subcommands("rewards", "transactions")
+    subcommands(
+        "rewards",
+        "transactions",
+        "export",
+        "transfer",
+        "ranking",
+        "audit",
+        "stats",
+        "admin"
+    )
 )]
 pub async fn economy(_: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-/// Consulte seu histórico recente de transações.
-#[poise::command(
-    slash_command,
-    prefix_command,
-    rename = "transações",
-    category = "Economia",
-    on_error = "crate::commands::util::command_error_handler",
-    ephemeral = true
-)]
-pub async fn transactions(ctx: Context<'_>) -> Result<(), Error> {
-    ctx.defer_ephemeral().await?;
-
-    let discord_id = ctx.author().id.get() as i64;
-    let entries = {
-        let db = ctx.data().database.clone();
-        let user = database::get_or_create_user(&db, discord_id).await?;
-        database::list_currency_transactions(&db, user.id, TRANSACTION_FETCH_LIMIT).await?
-    };
-
-    if entries.is_empty() {
-        ctx.send(poise::CreateReply::default().content(pretty_message(
-            icon::EMPTY,
-            "Ainda não há transações registradas para sua conta.",
-        )))
-        .await?;
-        return Ok(());
-    }
-
-    let pages = build_transaction_pages(&entries);
-    paginate_transaction_pages(ctx, pages).await
-}
-
 /// Resgate suas recompensas!
 #[poise::command(
     slash_command,
@@ -122,65 +109,100 @@ pub async fn rewards(ctx: Context<'_>) -> Result<(), Error> {
         };
 
         let now = Utc::now();
-        let state_snapshot = RewardStateLookup::new(&reward_states).get(kind).cloned();
-        let available = is_reward_available(state_snapshot.as_ref(), now);
-        let response_text;
+        let db = ctx.data().database.clone();
+        // Re-read the state: another message or a double click may have claimed it already
+        let state_snapshot = database::reward::get(&db, user.id, kind.db_name()).await?;
+        if let Some(state) = state_snapshot.clone() {
+            replace_reward_state(&mut reward_states, kind, state);
+        }
+
+        let response_text = if is_reward_available(state_snapshot.as_ref(), now) {
+            let streak = streak::next_streak(state_snapshot.as_ref(), kind, now);
+            let bonus_percent = streak::bonus_percent(kind, streak);
+            let (base_money, diamonds) = roll_reward(kind);
+            let money = streak::apply_bonus(base_money, bonus_percent);
+            let milestone = kind.milestone_diamonds(streak);
+            let next_reset = time::next_reset_from(now, kind.reset_period(), &RESET_CONFIG);
 
-        if available {
-            let (money, diamonds) = roll_reward(kind);
-            user.dollars += money;
+            let context = format!("reward:{}", kind.db_name());
+            let mut payouts = Vec::with_capacity(2);
+            if money != 0 {
+                payouts.push(
+                    WalletChange::new(Currency::Dollars, money, "reward_claim")
+                        .with_context(context.clone()),
+                );
+            }
             if let Some(amount) = diamonds {
-                user.diamonds += amount;
+                payouts.push(
+                    WalletChange::new(Currency::Diamonds, amount, "reward_claim")
+                        .with_context(context.clone()),
+                );
+            }
+            if let Some(amount) = milestone {
+                payouts.push(
+                    WalletChange::new(Currency::Diamonds, amount, "reward_milestone")
+                        .with_context(format!("{context}:streak:{streak}")),
+                );
             }
 
-            let total_claims = state_snapshot
-                .as_ref()
-                .map(|s| s.total_claims + 1)
-                .unwrap_or(1);
-            let next_reset = time::next_reset_from(now, kind.reset_period(), &RESET_CONFIG);
-
-            {
-                let db = ctx.data().database.clone();
-                user = database::update_user_balance(&db, user.id, user.dollars, user.diamonds)
-                    .await?;
-                if money != 0 {
-                    let context = format!("reward:{}", kind.db_name());
-                    database::insert_currency_transaction(
+            let claim = RewardClaim {
+                reward_type: kind.db_name(),
+                claimed_at: now,
+                next_reset_at: next_reset,
+                streak,
+                payouts,
+            };
+            match database::reward::claim(&db, user.id, claim).await? {
+                Some((new_state, updated)) => {
+                    user = updated;
+                    replace_reward_state(&mut reward_states, kind, new_state);
+
+                    let mut loan_deduction = None;
+                    if money > 0
+                        && let Some(repayment) =
+                            database::loan::repay(&db, user.id, money / 2, "loan_autorepay").await?
+                    {
+                        loan_deduction = Some(repayment.amount);
+                        user = repayment.user;
+                    }
+
+                    let unlocked = achievements::record(
                         &db,
-                        user.id,
-                        money,
-                        user.dollars,
-                        "dollars",
-                        "reward_claim",
-                        Some(context),
+                        discord_id,
+                        AchievementEvent::RewardStreak {
+                            reward_type: kind.db_name(),
+                            streak,
+                        },
                     )
                     .await?;
+                    if !unlocked.is_empty() {
+                        user = database::get_or_create_user(&db, discord_id).await?;
+                    }
+
+                    let claim_message = format_claim_message(
+                        money,
+                        diamonds,
+                        streak,
+                        bonus_percent,
+                        milestone,
+                        loan_deduction,
+                    );
+                    match achievements::format_unlocks(&unlocked) {
+                        Some(lines) => format!("{claim_message}\n{lines}"),
+                        None => claim_message,
+                    }
+                }
+                None => {
+                    let latest = database::reward::get(&db, user.id, kind.db_name()).await?;
+                    if let Some(state) = latest.clone() {
+                        replace_reward_state(&mut reward_states, kind, state);
+                    }
+                    unavailable_message(latest.as_ref())
                 }
-                let new_state = database::upsert_reward_state(
-                    &db,
-                    user.id,
-                    kind.db_name(),
-                    Some(now),
-                    Some(next_reset),
-                    total_claims,
-                )
-                .await?;
-
-                replace_reward_state(&mut reward_states, kind, new_state);
             }
-
-            response_text = format_claim_message(money, diamonds);
         } else {
-            response_text = if let Some(state) = state_snapshot.as_ref() {
-                if let Some(next_time) = state.next_reset_datetime() {
-                    format_cooldown_message(next_time)
-                } else {
-                    pretty_message(icon::ERROR, "Erro ao verificar cooldown")
-                }
-            } else {
-                pretty_message(icon::ERROR, "Estado de recompensa não encontrado")
-            };
-        }
+            unavailable_message(state_snapshot.as_ref())
+        };
 
         let (embed, components) =
             build_rewards_message(&user, &reward_states, now, Some(&response_text));
@@ -289,20 +311,25 @@ fn reward_field(state: Option<&RewardStateModel>, kind: RewardKind, now: DateTim
     let available = is_reward_available(state, now);
     let (min_cash, max_cash) = kind.money_range();
     let payout_line = pretty_message(icon::DOLLAR, format!("{} - {} moedas", min_cash, max_cash));
+    let current = streak::current_streak(state, kind, now);
+    let streak_line = pretty_message(
+        icon::PLUS,
+        format_streak(current, streak::bonus_percent(kind, current)),
+    );
 
     if available {
         let upcoming_reset = time::next_reset_from(now, kind.reset_period(), &RESET_CONFIG);
         let reset_line = pretty_message(icon::ALARM, time::describe_absolute(upcoming_reset));
         let ready_line = pretty_message(icon::CHECK, "Pronto para coletar");
-        format!("{ready_line}\n{payout_line}\n{reset_line}")
+        format!("{ready_line}\n{payout_line}\n{streak_line}\n{reset_line}")
     } else if let Some(state) = state {
         if let Some(next_time) = state.next_reset_datetime() {
             let cooldown_line = pretty_message(icon::TIMER, time::describe_relative(next_time));
             let reset_line = pretty_message(icon::ALARM, time::describe_absolute(next_time));
-            format!("{payout_line}\n{reset_line}\n{cooldown_line}")
+            format!("{payout_line}\n{streak_line}\n{reset_line}\n{cooldown_line}")
         } else {
             format!(
-                "{payout_line}\n{}",
+                "{payout_line}\n{streak_line}\n{}",
                 pretty_message(icon::ERROR, "Erro no cooldown")
             )
         }
@@ -310,7 +337,17 @@ fn reward_field(state: Option<&RewardStateModel>, kind: RewardKind, now: DateTim
         let upcoming_reset = time::next_reset_from(now, kind.reset_period(), &RESET_CONFIG);
         let reset_line = pretty_message(icon::ALARM, time::describe_absolute(upcoming_reset));
         let ready_line = pretty_message(icon::CHECK, "Pronto para coletar");
-        format!("{ready_line}\n{payout_line}\n{reset_line}")
+        format!("{ready_line}\n{payout_line}\n{streak_line}\n{reset_line}")
+    }
+}
+
+fn unavailable_message(state: Option<&RewardStateModel>) -> String {
+    match state {
+        Some(state) => match state.next_reset_datetime() {
+            Some(next_time) => format_cooldown_message(next_time),
+            None => pretty_message(icon::ERROR, "Erro ao verificar cooldown"),
+        },
+        None => pretty_message(icon::ERROR, "Estado de recompensa não encontrado"),
     }
 }
 
@@ -336,15 +373,45 @@ fn roll_reward(kind: RewardKind) -> (i64, Option<i64>) {
     (money, diamonds)
 }
 
-fn format_claim_message(money: i64, diamonds: Option<i64>) -> String {
+fn format_claim_message(
+    money: i64,
+    diamonds: Option<i64>,
+    streak: i64,
+    bonus_percent: i64,
+    milestone: Option<i64>,
+    loan_deduction: Option<i64>,
+) -> String {
     let money_line = pretty_message(icon::DOLLAR, format!("+{money} moedas"));
     let diamond_line = diamonds
         .map(|amount| pretty_message(icon::DIAMOND, format!("+{amount} diamantes")))
         .unwrap_or_else(|| pretty_message(icon::DIAMOND, "Sem diamantes desta vez"));
-    format!(
-        "{}\n{money_line}\n{diamond_line}",
-        pretty_message(icon::GIFT, "Recompensa coletada")
-    )
+    let mut lines = vec![
+        pretty_message(icon::GIFT, "Recompensa coletada"),
+        money_line,
+        diamond_line,
+        pretty_message(icon::PLUS, format_streak(streak, bonus_percent)),
+    ];
+    if let Some(amount) = milestone {
+        lines.push(pretty_message(
+            icon::DIAMOND,
+            format!("Marco de {streak} seguidas: +{amount} diamantes"),
+        ));
+    }
+    if let Some(amount) = loan_deduction {
+        lines.push(pretty_message(
+            icon::MINUS,
+            format!("-{amount} moedas abatidas do empréstimo"),
+        ));
+    }
+    lines.join("\n")
+}
+
+fn format_streak(streak: i64, bonus_percent: i64) -> String {
+    if bonus_percent > 0 {
+        format!("Sequência: {streak} (+{bonus_percent}% moedas)")
+    } else {
+        format!("Sequência: {streak}")
+    }
 }
 
 fn format_cooldown_message(next_time: DateTime<Utc>) -> String {
@@ -357,106 +424,3 @@ fn format_cooldown_message(next_time: DateTime<Utc>) -> String {
         relative
     )
 }
-
-fn build_transaction_pages(entries: &[CurrencyTransactionModel]) -> Vec<String> {
-    entries
-        .chunks(TRANSACTION_PAGE_SIZE)
-        .map(|chunk| {
-            chunk
-                .iter()
-                .map(format_transaction_entry)
-                .collect::<Vec<_>>()
-                .join("\n\n")
-        })
-        .collect()
-}
-
-fn format_transaction_entry(entry: &CurrencyTransactionModel) -> String {
-    let direction_icon = if entry.amount >= 0 {
-        icon::PLUS
-    } else {
-        icon::MINUS
-    };
-
-    let currency_label = describe_currency(&entry.currency);
-    let amount_display = format_currency(entry.amount.abs());
-    let balance_display = format_currency(entry.balance_after);
-    let timestamp = DateTime::parse_from_rfc3339(&entry.created_at)
-        .ok()
-        .map(|dt| time::describe_relative(dt.with_timezone(&Utc)))
-        .unwrap_or_else(|| "momento desconhecido".to_string());
-
-    let mut lines = vec![
-        format!(
-            "{} {}",
-            direction_icon,
-            bold(format!("{amount_display} {currency_label}"))
-        ),
-        format!(
-            "Saldo após: {}",
-            bold(format!("{balance_display} {currency_label}"))
-        ),
-        format!("Tipo: {} • {}", inline_code(&entry.kind), timestamp),
-    ];
-
-    if let Some(context) = &entry.context {
-        if !context.is_empty() {
-            lines.push(format!("Contexto: {}", italic(context)));
-        }
-    }
-
-    lines.join("\n")
-}
-
-fn describe_currency(code: &str) -> String {
-    match code {
-        "dollars" => "moedas".to_string(),
-        "diamonds" => "diamantes".to_string(),
-        other => other.to_string(),
-    }
-}
-
-async fn paginate_transaction_pages(ctx: Context<'_>, pages: Vec<String>) -> Result<(), Error> {
-    if pages.is_empty() {
-        return Ok(());
-    }
-
-    let total_pages = pages.len();
-    let author_name = ctx.author().name.clone();
-
-    paginate(
-        ctx,
-        total_pages,
-        TRANSACTION_TIMEOUT,
-        true,
-        0,
-        move |current_page, total_pages| {
-            let embed = build_transactions_embed(
-                &author_name,
-                &pages[current_page],
-                current_page,
-                total_pages,
-            );
-            (embed, Vec::new())
-        },
-    )
-    .await
-}
-
-fn build_transactions_embed(
-    author_name: &str,
-    page_content: &str,
-    current_page: usize,
-    total_pages: usize,
-) -> serenity::CreateEmbed {
-    serenity::CreateEmbed::new()
-        .title(format!("{} Histórico de {}", icon::HOUSE, author_name))
-        .colour(colors::MOON)
-        .description(page_content.to_string())
-        .footer(CreateEmbedFooter::new(format!(
-            "Página {}/{} • últimas {} transações",
-            current_page + 1,
-            total_pages,
-            TRANSACTION_FETCH_LIMIT
-        )))
-}
@@ -0,0 +1,183 @@
+use crate::{
+    Context, Error,
+    constants::{CustomEmoji, colors, icon},
+    database::{self, LeaderboardEntryModel, LeaderboardMetric},
+    functions::{
+        format::{
+            discord::{bold, mention},
+            format_currency, pretty_message,
+        },
+        interactions::pagination::paginate,
+    },
+};
+use poise::serenity_prelude as serenity;
+use serenity::builder::CreateEmbedFooter;
+use std::time::Duration;
+
+const RANKING_FETCH_LIMIT: i64 = 100;
+const RANKING_PAGE_SIZE: usize = 10;
+const RANKING_TIMEOUT: Duration = Duration::from_secs(180);
+
+#[derive(Clone, Copy, poise::ChoiceParameter)]
+pub enum RankingOrder {
+    #[name = "Moedas"]
+    Dollars,
+    #[name = "Diamantes"]
+    Diamonds,
+    #[name = "Recompensas coletadas"]
+    RewardClaims,
+}
+
+impl RankingOrder {
+    fn metric(self) -> LeaderboardMetric {
+        match self {
+            Self::Dollars => LeaderboardMetric::Dollars,
+            Self::Diamonds => LeaderboardMetric::Diamonds,
+            Self::RewardClaims => LeaderboardMetric::RewardClaims,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Dollars => "Mais ricos em moedas",
+            Self::Diamonds => "Mais ricos em diamantes",
+            Self::RewardClaims => "Mais recompensas coletadas",
+        }
+    }
+
+    fn icon(self) -> CustomEmoji {
+        match self {
+            Self::Dollars => icon::DOLLAR,
+            Self::Diamonds => icon::DIAMOND,
+            Self::RewardClaims => icon::GIFT,
+        }
+    }
+
+    fn format_value(self, value: i64) -> String {
+        match self {
+            Self::Dollars => format!("{} moedas", format_currency(value)),
+            Self::Diamonds => format!("{} diamantes", format_currency(value)),
+            Self::RewardClaims => format!("{} resgates", format_currency(value)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, poise::ChoiceParameter)]
+pub enum RankingScope {
+    #[name = "Global"]
+    Global,
+    #[name = "Este servidor"]
+    Guild,
+}
+
+/// Veja quem está no topo da economia.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "ranking",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler"
+)]
+pub async fn ranking(
+    ctx: Context<'_>,
+    #[description = "Critério de ordenação (padrão: moedas)"] ordem: Option<RankingOrder>,
+    #[description = "Global ou apenas este servidor (padrão: global)"] escopo: Option<RankingScope>,
+) -> Result<(), Error> {
+    let order = ordem.unwrap_or(RankingOrder::Dollars);
+    let guild_id = match escopo.unwrap_or(RankingScope::Global) {
+        RankingScope::Global => None,
+        RankingScope::Guild => ctx.guild_id().map(|id| id.get() as i64),
+    };
+
+    let db = ctx.data().database.clone();
+    let entries =
+        database::leaderboard::top(&db, order.metric(), guild_id, RANKING_FETCH_LIMIT).await?;
+
+    if entries.is_empty() {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(
+                    icon::EMPTY,
+                    "Ainda não há ninguém neste ranking.",
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let author = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let own_entry =
+        database::leaderboard::find_position(&db, order.metric(), guild_id, author.id).await?;
+    let footer_rank = if order.metric().is_balance()
+        && database::settings::get(&db, author.id).await?.hide_balance
+    {
+        "Seu saldo está oculto por privacidade".to_string()
+    } else {
+        own_rank_text(order, own_entry.as_ref())
+    };
+    let scope_label = if guild_id.is_some() {
+        "Este servidor"
+    } else {
+        "Global"
+    };
+
+    let pages = build_ranking_pages(order, &entries);
+    paginate(
+        ctx,
+        pages.len(),
+        RANKING_TIMEOUT,
+        false,
+        0,
+        move |current_page, total_pages| {
+            let embed = serenity::CreateEmbed::new()
+                .title(format!(
+                    "{} {} • {}",
+                    order.icon(),
+                    order.title(),
+                    scope_label
+                ))
+                .colour(colors::MOON)
+                .description(pages[current_page].clone())
+                .footer(CreateEmbedFooter::new(format!(
+                    "Página {}/{} • {}",
+                    current_page + 1,
+                    total_pages,
+                    footer_rank
+                )));
+            (embed, Vec::new())
+        },
+    )
+    .await
+}
+
+fn build_ranking_pages(order: RankingOrder, entries: &[LeaderboardEntryModel]) -> Vec<String> {
+    entries
+        .chunks(RANKING_PAGE_SIZE)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|entry| {
+                    format!(
+                        "{} {} — {}",
+                        bold(format!("{}º", entry.position)),
+                        mention(entry.discord_id),
+                        order.format_value(entry.value)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect()
+}
+
+fn own_rank_text(order: RankingOrder, entry: Option<&LeaderboardEntryModel>) -> String {
+    match entry {
+        Some(entry) => format!(
+            "Sua posição: {}º ({})",
+            entry.position,
+            order.format_value(entry.value)
+        ),
+        None => "Você ainda não aparece neste ranking".to_string(),
+    }
+}
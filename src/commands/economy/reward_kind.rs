@@ -59,6 +59,25 @@ impl RewardKind {
         }
     }
 
+    /// Extra payout percentage gained per consecutive claim and its ceiling
+    pub fn streak_bonus(self) -> (i64, i64) {
+        match self {
+            Self::Daily => (5, 50),
+            Self::Weekly => (10, 50),
+            Self::Monthly => (10, 30),
+        }
+    }
+
+    /// Diamonds granted when a streak reaches a full week or month of consecutive claims
+    pub fn milestone_diamonds(self, streak: i64) -> Option<i64> {
+        match self {
+            Self::Daily if streak % 30 == 0 => Some(25),
+            Self::Daily if streak % 7 == 0 => Some(5),
+            Self::Weekly if streak % 4 == 0 => Some(10),
+            _ => None,
+        }
+    }
+
     pub fn from_custom_id(id: &str) -> Option<Self> {
         Self::ALL.into_iter().find(|kind| kind.custom_id() == id)
     }
@@ -0,0 +1,168 @@
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{self, EconomyWindowModel},
+    functions::format::{discord::bold, format_currency},
+};
+use chrono::{Duration, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::builder::CreateEmbedFooter;
+
+#[derive(Clone, Copy, poise::ChoiceParameter)]
+pub enum StatsWindow {
+    #[name = "Últimas 24 horas"]
+    Day,
+    #[name = "Últimos 7 dias"]
+    Week,
+    #[name = "Últimos 30 dias"]
+    Month,
+}
+
+impl StatsWindow {
+    fn duration(self) -> Duration {
+        match self {
+            Self::Day => Duration::days(1),
+            Self::Week => Duration::days(7),
+            Self::Month => Duration::days(30),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Day => "últimas 24 horas",
+            Self::Week => "últimos 7 dias",
+            Self::Month => "últimos 30 dias",
+        }
+    }
+}
+
+/// Mostra a oferta de moedas e o desempenho da casa.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "estatísticas",
+    category = "Equipe",
+    owners_only,
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn stats(
+    ctx: Context<'_>,
+    #[description = "Período analisado (padrão: últimos 7 dias)"] periodo: Option<StatsWindow>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let window = periodo.unwrap_or(StatsWindow::Week);
+    let until = Utc::now();
+    let since = until - window.duration();
+    let previous_since = since - window.duration();
+
+    let db = ctx.data().database.clone();
+    let current = database::stats::window_summary(&db, since, until).await?;
+    let previous = database::stats::window_summary(&db, previous_since, since).await?;
+    let supply = database::stats::supply(&db).await?;
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("{} Estatísticas da economia", icon::HAMMER))
+        .colour(colors::MOON)
+        .description(format!(
+            "Período: {} • comparado ao período anterior",
+            bold(window.label())
+        ))
+        .field(
+            "Emitido em recompensas",
+            [
+                metric_line(icon::DOLLAR, "Moedas", &current, &previous, |w| {
+                    w.minted_dollars
+                }),
+                metric_line(icon::DIAMOND, "Diamantes", &current, &previous, |w| {
+                    w.minted_diamonds
+                }),
+            ]
+            .join("\n"),
+            false,
+        )
+        .field(
+            "Mines",
+            [
+                metric_line(icon::MINUS, "Apostado", &current, &previous, |w| {
+                    w.mines_wagered
+                }),
+                metric_line(icon::PLUS, "Pago em resgates", &current, &previous, |w| {
+                    w.mines_paid_out
+                }),
+                metric_line(icon::HOUSE, "Lucro da casa", &current, &previous, |w| {
+                    w.mines_house_net()
+                }),
+            ]
+            .join("\n"),
+            false,
+        )
+        .field(
+            "Oferta em circulação",
+            [
+                format!(
+                    "{} Moedas: {} • {}",
+                    icon::DOLLAR,
+                    bold(format_currency(supply.dollars)),
+                    format_delta(current.net_dollars, previous.net_dollars)
+                ),
+                format!(
+                    "{} Diamantes: {} • {}",
+                    icon::DIAMOND,
+                    bold(format_currency(supply.diamonds)),
+                    format_delta(current.net_diamonds, previous.net_diamonds)
+                ),
+            ]
+            .join("\n"),
+            false,
+        )
+        .field(
+            "Usuários",
+            [
+                metric_line(icon::BELL, "Ativos", &current, &previous, |w| {
+                    w.active_users
+                }),
+                format!(
+                    "{} Cadastrados: {}",
+                    icon::HASTAG,
+                    bold(format_currency(supply.users))
+                ),
+            ]
+            .join("\n"),
+            false,
+        )
+        .footer(CreateEmbedFooter::new(
+            "Oferta: saldo atual nas carteiras e variação líquida no período",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+fn metric_line(
+    emoji: impl std::fmt::Display,
+    label: &str,
+    current: &EconomyWindowModel,
+    previous: &EconomyWindowModel,
+    value: impl Fn(&EconomyWindowModel) -> i64,
+) -> String {
+    let now = value(current);
+    format!(
+        "{emoji} {label}: {} ({})",
+        bold(format_currency(now)),
+        format_delta(now, value(previous))
+    )
+}
+
+/// Change against the previous window, e.g. `+1.200 / +12%`
+fn format_delta(current: i64, previous: i64) -> String {
+    let delta = current - previous;
+    let sign = if delta > 0 { "+" } else { "" };
+    if previous == 0 {
+        return format!("{sign}{}", format_currency(delta));
+    }
+
+    let percent = delta as f64 / previous.abs() as f64 * 100.0;
+    format!("{sign}{} / {percent:+.0}%", format_currency(delta))
+}
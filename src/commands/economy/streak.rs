@@ -0,0 +1,41 @@
+use super::{RESET_CONFIG, reward_kind::RewardKind};
+use crate::{database::RewardStateModel, functions::time};
+use chrono::{DateTime, Utc};
+
+/// Latest moment a claim still continues the streak: one full period after the reward became available
+fn streak_deadline(state: &RewardStateModel, kind: RewardKind) -> Option<DateTime<Utc>> {
+    state
+        .next_reset_datetime()
+        .map(|ready_at| time::next_reset_from(ready_at, kind.reset_period(), &RESET_CONFIG))
+}
+
+/// Streak that is still alive at `now`, or zero if the user let it lapse
+pub fn current_streak(
+    state: Option<&RewardStateModel>,
+    kind: RewardKind,
+    now: DateTime<Utc>,
+) -> i64 {
+    let Some(state) = state else {
+        return 0;
+    };
+
+    match streak_deadline(state, kind) {
+        Some(deadline) if now < deadline => state.streak,
+        _ => 0,
+    }
+}
+
+/// Streak the user will have after claiming at `now`
+pub fn next_streak(state: Option<&RewardStateModel>, kind: RewardKind, now: DateTime<Utc>) -> i64 {
+    current_streak(state, kind, now) + 1
+}
+
+/// Bonus percentage applied to a claim made with the given streak
+pub fn bonus_percent(kind: RewardKind, streak: i64) -> i64 {
+    let (step, cap) = kind.streak_bonus();
+    ((streak - 1).max(0) * step).min(cap)
+}
+
+pub fn apply_bonus(amount: i64, percent: i64) -> i64 {
+    amount + amount * percent / 100
+}
@@ -0,0 +1,423 @@
+use super::{RESET_CONFIG, currency_choice::CurrencyChoice};
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{
+        self, CurrencyTransactionModel, TransactionFilter, TransactionSign, TransactionTotalsModel,
+    },
+    functions::{
+        format::{
+            discord::{bold, inline_code, italic},
+            format_currency, pretty_message,
+        },
+        interactions::pagination::paginate_async,
+        time,
+    },
+};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::builder::{CreateAutocompleteResponse, CreateEmbedFooter};
+use sqlx::sqlite::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+const TRANSACTION_PAGE_SIZE: i64 = 5;
+const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(180);
+const KIND_SUGGESTION_LIMIT: i64 = 25;
+
+#[derive(Clone, Copy, poise::ChoiceParameter)]
+pub enum SignChoice {
+    #[name = "Somente ganhos"]
+    Gains,
+    #[name = "Somente perdas"]
+    Losses,
+}
+
+impl SignChoice {
+    fn sign(self) -> TransactionSign {
+        match self {
+            Self::Gains => TransactionSign::Gains,
+            Self::Losses => TransactionSign::Losses,
+        }
+    }
+}
+
+/// Consulte seu histórico de transações.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "transações",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn transactions(
+    ctx: Context<'_>,
+    #[description = "Apenas transações nesta moeda"] moeda: Option<CurrencyChoice>,
+    #[description = "Apenas transações deste tipo"]
+    #[autocomplete = "autocomplete_kind"]
+    tipo: Option<String>,
+    #[description = "Data inicial (DD/MM/AAAA)"] desde: Option<String>,
+    #[description = "Data final, inclusiva (DD/MM/AAAA)"] ate: Option<String>,
+    #[description = "Mostrar apenas ganhos ou perdas"] sinal: Option<SignChoice>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let has_filters =
+        moeda.is_some() || tipo.is_some() || desde.is_some() || ate.is_some() || sinal.is_some();
+    let mut filter = match build_filter(tipo, desde.as_deref(), ate.as_deref()) {
+        Ok(filter) => filter,
+        Err(message) => {
+            ctx.send(poise::CreateReply::default().content(pretty_message(icon::ERROR, message)))
+                .await?;
+            return Ok(());
+        }
+    };
+    filter.currency = moeda.map(|choice| choice.currency().db_name().to_string());
+    filter.sign = sinal.map(SignChoice::sign);
+
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let totals = database::transaction::totals_by_currency(&db, user.id, &filter).await?;
+    let total_count: i64 = totals.iter().map(|total| total.count).sum();
+
+    if total_count == 0 {
+        let message = if has_filters {
+            "Nenhuma transação encontrada com esses filtros."
+        } else {
+            "Ainda não há transações registradas para sua conta."
+        };
+        ctx.send(poise::CreateReply::default().content(pretty_message(icon::EMPTY, message)))
+            .await?;
+        return Ok(());
+    }
+
+    let total_pages = ((total_count + TRANSACTION_PAGE_SIZE - 1) / TRANSACTION_PAGE_SIZE) as usize;
+    let author_name = Arc::new(ctx.author().name.clone());
+    let totals_text = Arc::new(format_totals(total_count, &totals));
+    let cursor = Arc::new(Mutex::new(TransactionCursor::new(
+        user.id,
+        filter,
+        total_count,
+    )));
+
+    paginate_async(
+        ctx,
+        total_pages,
+        TRANSACTION_TIMEOUT,
+        true,
+        0,
+        move |current_page, total_pages| {
+            let db = db.clone();
+            let cursor = cursor.clone();
+            let author_name = author_name.clone();
+            let totals_text = totals_text.clone();
+            async move {
+                let entries = cursor.lock().await.load(&db, current_page).await?;
+                let page = TransactionPage::load(&db, entries).await?;
+                let embed = build_transactions_embed(
+                    &author_name,
+                    &page.render(),
+                    current_page,
+                    total_pages,
+                    &totals_text,
+                );
+                Ok((embed, Vec::new()))
+            }
+        },
+    )
+    .await
+}
+
+/// Builds a ledger filter from user input; the `until` day is included in the range
+pub(super) fn build_filter(
+    kind: Option<String>,
+    since: Option<&str>,
+    until: Option<&str>,
+) -> Result<TransactionFilter, &'static str> {
+    let since = since
+        .map(|value| time::parse_date(value).ok_or("Data inicial inválida. Use DD/MM/AAAA."))
+        .transpose()?;
+    let until = until
+        .map(|value| time::parse_date(value).ok_or("Data final inválida. Use DD/MM/AAAA."))
+        .transpose()?;
+
+    if let (Some(since), Some(until)) = (since, until)
+        && since > until
+    {
+        return Err("A data inicial precisa ser anterior à data final.");
+    }
+
+    Ok(TransactionFilter {
+        kind: kind
+            .map(|kind| kind.trim().to_string())
+            .filter(|kind| !kind.is_empty()),
+        since: since.map(|date| time::start_of_day(date, &RESET_CONFIG)),
+        until: until.map(|date| time::start_of_day(date, &RESET_CONFIG) + ChronoDuration::days(1)),
+        ..TransactionFilter::default()
+    })
+}
+
+/// Keyset cursor over a filtered history. Pages are fetched relative to the
+/// boundaries of the page currently on screen instead of with offsets
+struct TransactionCursor {
+    user_id: i32,
+    filter: TransactionFilter,
+    total_count: i64,
+    page: usize,
+    newest_id: Option<i32>,
+    oldest_id: Option<i32>,
+}
+
+impl TransactionCursor {
+    fn new(user_id: i32, filter: TransactionFilter, total_count: i64) -> Self {
+        Self {
+            user_id,
+            filter,
+            total_count,
+            page: 0,
+            newest_id: None,
+            oldest_id: None,
+        }
+    }
+
+    async fn load(
+        &mut self,
+        db: &SqlitePool,
+        target: usize,
+    ) -> Result<Vec<CurrencyTransactionModel>, Error> {
+        let last_page = ((self.total_count - 1) / TRANSACTION_PAGE_SIZE) as usize;
+
+        let entries = if target == 0 {
+            self.page_before(db, None, TRANSACTION_PAGE_SIZE).await?
+        } else if target == last_page {
+            let remaining = self.total_count - target as i64 * TRANSACTION_PAGE_SIZE;
+            self.oldest_page(db, remaining).await?
+        } else if target == self.page + 1 {
+            self.page_before(db, self.oldest_id, TRANSACTION_PAGE_SIZE)
+                .await?
+        } else if target + 1 == self.page {
+            self.previous_page(db).await?
+        } else {
+            self.page_before(db, self.newest_id.map(|id| id + 1), TRANSACTION_PAGE_SIZE)
+                .await?
+        };
+
+        self.page = target;
+        self.newest_id = entries.first().map(|entry| entry.id);
+        self.oldest_id = entries.last().map(|entry| entry.id);
+        Ok(entries)
+    }
+
+    /// Newest-first entries with an id below `before_id`
+    async fn page_before(
+        &self,
+        db: &SqlitePool,
+        before_id: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<CurrencyTransactionModel>, Error> {
+        Ok(
+            database::transaction::list_before(db, self.user_id, &self.filter, before_id, limit)
+                .await?,
+        )
+    }
+
+    /// The page right before the current one: the oldest entries newer than what is shown
+    async fn previous_page(&self, db: &SqlitePool) -> Result<Vec<CurrencyTransactionModel>, Error> {
+        let after_id = self.newest_id.unwrap_or_default();
+        let mut entries = database::transaction::list_after(
+            db,
+            self.user_id,
+            &self.filter,
+            after_id,
+            TRANSACTION_PAGE_SIZE,
+        )
+        .await?;
+        entries.reverse();
+        Ok(entries)
+    }
+
+    /// The oldest `count` entries, displayed newest first
+    async fn oldest_page(
+        &self,
+        db: &SqlitePool,
+        count: i64,
+    ) -> Result<Vec<CurrencyTransactionModel>, Error> {
+        let mut entries =
+            database::transaction::list_after(db, self.user_id, &self.filter, 0, count).await?;
+        entries.reverse();
+        Ok(entries)
+    }
+}
+
+async fn autocomplete_kind(ctx: Context<'_>, partial: &str) -> CreateAutocompleteResponse {
+    let db = ctx.data().database.clone();
+    let kinds = database::transaction::list_kinds(&db, partial.trim(), KIND_SUGGESTION_LIMIT)
+        .await
+        .unwrap_or_default();
+
+    kinds
+        .into_iter()
+        .fold(CreateAutocompleteResponse::new(), |acc, kind| {
+            acc.add_string_choice(kind.clone(), kind)
+        })
+}
+
+fn format_totals(total_count: i64, totals: &[TransactionTotalsModel]) -> String {
+    let mut parts = vec![format!("{total_count} transações")];
+    for total in totals {
+        let sign = if total.net > 0 { "+" } else { "" };
+        parts.push(format!(
+            "{}: {sign}{}",
+            describe_currency(&total.currency),
+            format_currency(total.net)
+        ));
+    }
+    parts.join(" • ")
+}
+
+/// Entries shown on one page together with the other half of any reversal pair,
+/// so a refund is rendered next to the entry it undoes
+struct TransactionPage {
+    entries: Vec<CurrencyTransactionModel>,
+    originals: HashMap<i32, CurrencyTransactionModel>,
+    reversals: HashMap<i32, CurrencyTransactionModel>,
+}
+
+impl TransactionPage {
+    async fn load(db: &SqlitePool, entries: Vec<CurrencyTransactionModel>) -> Result<Self, Error> {
+        let original_ids: Vec<i32> = entries
+            .iter()
+            .filter_map(|entry| entry.reversal_of)
+            .collect();
+        let entry_ids: Vec<i32> = entries.iter().map(|entry| entry.id).collect();
+
+        let originals = database::transaction::list_by_ids(db, &original_ids)
+            .await?
+            .into_iter()
+            .map(|entry| (entry.id, entry))
+            .collect();
+        let reversals = database::transaction::list_reversals_of(db, &entry_ids)
+            .await?
+            .into_iter()
+            .filter_map(|entry| entry.reversal_of.map(|original| (original, entry)))
+            .collect();
+
+        Ok(Self {
+            entries,
+            originals,
+            reversals,
+        })
+    }
+
+    fn render(&self) -> String {
+        let on_page: HashSet<i32> = self.entries.iter().map(|entry| entry.id).collect();
+
+        self.entries
+            .iter()
+            .filter(|entry| {
+                // Reversed entries on this page are rendered inside their reversal block
+                self.reversals
+                    .get(&entry.id)
+                    .is_none_or(|reversal| !on_page.contains(&reversal.id))
+            })
+            .map(|entry| {
+                let mut block = format_transaction_entry(entry);
+                if let Some(original) = entry
+                    .reversal_of
+                    .and_then(|original_id| self.originals.get(&original_id))
+                {
+                    block.push_str(&format!(
+                        "\n↩ Estorno de {}: {} • {}",
+                        inline_code(format!("#{}", original.id)),
+                        format_signed_amount(original),
+                        inline_code(&original.kind)
+                    ));
+                } else if let Some(reversal) = self.reversals.get(&entry.id) {
+                    block.push_str(&format!(
+                        "\n↩ Estornada por {}",
+                        inline_code(format!("#{}", reversal.id))
+                    ));
+                }
+                block
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+fn format_signed_amount(entry: &CurrencyTransactionModel) -> String {
+    let sign = if entry.amount >= 0 { "+" } else { "-" };
+    format!(
+        "{sign}{} {}",
+        format_currency(entry.amount.abs()),
+        describe_currency(&entry.currency)
+    )
+}
+
+fn format_transaction_entry(entry: &CurrencyTransactionModel) -> String {
+    let direction_icon = if entry.amount >= 0 {
+        icon::PLUS
+    } else {
+        icon::MINUS
+    };
+
+    let currency_label = describe_currency(&entry.currency);
+    let amount_display = format_currency(entry.amount.abs());
+    let balance_display = format_currency(entry.balance_after);
+    let timestamp = DateTime::parse_from_rfc3339(&entry.created_at)
+        .ok()
+        .map(|dt| time::describe_relative(dt.with_timezone(&Utc)))
+        .unwrap_or_else(|| "momento desconhecido".to_string());
+
+    let mut lines = vec![
+        format!(
+            "{} {}",
+            direction_icon,
+            bold(format!("{amount_display} {currency_label}"))
+        ),
+        format!(
+            "Saldo após: {}",
+            bold(format!("{balance_display} {currency_label}"))
+        ),
+        format!("Tipo: {} • {}", inline_code(&entry.kind), timestamp),
+    ];
+
+    if let Some(context) = &entry.context
+        && !context.is_empty()
+    {
+        lines.push(format!("Contexto: {}", italic(context)));
+    }
+
+    lines.join("\n")
+}
+
+fn describe_currency(code: &str) -> String {
+    match code {
+        "dollars" => "moedas".to_string(),
+        "diamonds" => "diamantes".to_string(),
+        "savings" => "moedas na poupança".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn build_transactions_embed(
+    author_name: &str,
+    page_content: &str,
+    current_page: usize,
+    total_pages: usize,
+    totals_text: &str,
+) -> serenity::CreateEmbed {
+    serenity::CreateEmbed::new()
+        .title(format!("{} Histórico de {}", icon::HOUSE, author_name))
+        .colour(colors::MOON)
+        .description(page_content.to_string())
+        .footer(CreateEmbedFooter::new(format!(
+            "Página {}/{} • {}",
+            current_page + 1,
+            total_pages,
+            totals_text
+        )))
+}
@@ -0,0 +1,177 @@
+use super::{RESET_CONFIG, currency_choice::CurrencyChoice};
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{self, wallet::TransferOutcome},
+    functions::{
+        format::{discord::bold, format_currency, pretty_message},
+        interactions::{
+            opponent::{OpponentValidationMessages, ensure_valid_opponent},
+            prompt::{ConfirmationOutcome, ConfirmationPromptOptions, confirmation_prompt},
+        },
+        time,
+    },
+};
+use chrono::Utc;
+use poise::serenity_prelude::{self as serenity, Mentionable};
+use serenity::builder::CreateEmbedFooter;
+use std::time::Duration;
+
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Envie moedas ou diamantes para outra pessoa.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "transferir",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler"
+)]
+pub async fn transfer(
+    ctx: Context<'_>,
+    #[description = "Quem vai receber a transferência"] usuario: serenity::User,
+    #[description = "Quantidade a enviar"] valor: i64,
+    #[description = "Moeda enviada (padrão: moedas)"] moeda: Option<CurrencyChoice>,
+) -> Result<(), Error> {
+    let validator = OpponentValidationMessages::new(
+        "Você não pode transferir para si mesmo.",
+        "Bots não precisam de dinheiro. Escolha um usuário humano.",
+    );
+    if !ensure_valid_opponent(&ctx, &usuario, validator).await? {
+        return Ok(());
+    }
+
+    if valor <= 0 {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(
+                    icon::ERROR,
+                    "O valor da transferência precisa ser positivo.",
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let choice = moeda.unwrap_or(CurrencyChoice::Dollars);
+    let daily_limit = match choice {
+        CurrencyChoice::Dollars => ctx.data().economy.transfer_limit_dollars,
+        CurrencyChoice::Diamonds => ctx.data().economy.transfer_limit_diamonds,
+    };
+    let amount_text = format!("{} {}", bold(format_currency(valor)), choice.label());
+
+    let mut prompt = ConfirmationPromptOptions::new(pretty_message(
+        icon::BELL,
+        format!(
+            "{}, confirma o envio de {} para {}?",
+            ctx.author().mention(),
+            amount_text,
+            usuario.mention()
+        ),
+    ));
+    prompt.timeout = CONFIRMATION_TIMEOUT;
+
+    let confirmation = confirmation_prompt(&ctx, ctx.author().id, prompt).await?;
+    if !matches!(confirmation.outcome, ConfirmationOutcome::Accepted) {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(icon::MINUS, "Transferência cancelada."))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let db = ctx.data().database.clone();
+    let sender = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let recipient = database::get_or_create_user(&db, usuario.id.get() as i64).await?;
+    let now = Utc::now();
+    let outcome = database::wallet::transfer(
+        &db,
+        &sender,
+        &recipient,
+        choice.currency(),
+        valor,
+        daily_limit,
+        time::last_daily_reset(now, &RESET_CONFIG),
+    )
+    .await?;
+
+    match outcome {
+        TransferOutcome::Completed {
+            sender,
+            outgoing_id,
+            incoming_id,
+        } => {
+            let balance = choice.currency().balance_of(&sender);
+            let embed = serenity::CreateEmbed::new()
+                .title(format!("{} Transferência concluída", icon::CHECK))
+                .colour(colors::MINT)
+                .description(
+                    [
+                        pretty_message(
+                            choice.icon(),
+                            format!(
+                                "{} enviou {} para {}.",
+                                ctx.author().mention(),
+                                amount_text,
+                                usuario.mention()
+                            ),
+                        ),
+                        pretty_message(
+                            icon::HASTAG,
+                            format!(
+                                "Seu saldo agora: {} {}",
+                                bold(format_currency(balance)),
+                                choice.label()
+                            ),
+                        ),
+                    ]
+                    .join("\n"),
+                )
+                .footer(CreateEmbedFooter::new(format!(
+                    "Transações #{outgoing_id} • #{incoming_id}"
+                )));
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        }
+        TransferOutcome::InsufficientFunds => {
+            ctx.send(
+                poise::CreateReply::default()
+                    .content(pretty_message(
+                        icon::ERROR,
+                        format!("Você não possui {} suficientes.", choice.label()),
+                    ))
+                    .ephemeral(true),
+            )
+            .await?;
+        }
+        TransferOutcome::LimitExceeded { remaining } => {
+            let reset_at = time::next_reset_from(now, time::ResetPeriod::Daily, &RESET_CONFIG);
+            ctx.send(
+                poise::CreateReply::default()
+                    .content(
+                        [
+                            pretty_message(
+                                icon::ERROR,
+                                format!(
+                                    "Limite diário atingido. Você ainda pode enviar {} {} hoje.",
+                                    bold(format_currency(remaining)),
+                                    choice.label()
+                                ),
+                            ),
+                            pretty_message(
+                                icon::ALARM,
+                                format!("O limite renova {}", time::describe_relative(reset_at)),
+                            ),
+                        ]
+                        .join("\n"),
+                    )
+                    .ephemeral(true),
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
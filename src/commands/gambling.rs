@@ -0,0 +1,276 @@
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{self, GamblingLimitsModel, gambling::LimitPeriod},
+    functions::{
+        bot::gambling::{COOLING_OFF, is_excluded, window},
+        format::{discord::bold, format_currency, pretty_message},
+        interactions::prompt::{
+            ConfirmationOutcome, ConfirmationPromptOptions, confirmation_prompt,
+        },
+        time,
+    },
+};
+use chrono::{DateTime, Duration, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::builder::CreateEmbedFooter;
+use std::time::Duration as StdDuration;
+
+const CONFIRMATION_TIMEOUT: StdDuration = StdDuration::from_secs(60);
+
+#[derive(Clone, Copy, poise::ChoiceParameter)]
+pub enum PeriodChoice {
+    #[name = "Diário"]
+    Daily,
+    #[name = "Semanal"]
+    Weekly,
+}
+
+impl PeriodChoice {
+    fn period(self) -> LimitPeriod {
+        match self {
+            Self::Daily => LimitPeriod::Daily,
+            Self::Weekly => LimitPeriod::Weekly,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Daily => "diário",
+            Self::Weekly => "semanal",
+        }
+    }
+}
+
+/// Controle seus limites de apostas.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "apostas",
+    category = "Economia",
+    interaction_context = "Guild",
+    on_error = "crate::commands::util::command_error_handler",
+    subcommands("gambling_limit", "gambling_exclusion", "gambling_status")
+)]
+pub async fn gambling(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Defina um limite de perdas em apostas.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "limite",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn gambling_limit(
+    ctx: Context<'_>,
+    #[description = "Período do limite"] periodo: PeriodChoice,
+    #[description = "Perda máxima no período (deixe vazio para remover o limite)"]
+    #[min = 0]
+    valor: Option<i64>,
+) -> Result<(), Error> {
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let now = Utc::now();
+    let limits = database::gambling::get(&db, user.id, now).await?;
+    let period = periodo.period();
+    let current = period.current(&limits);
+
+    // Tightening a limit is always safe; loosening it has to wait out the cooling-off
+    let tightens = match (current, valor) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(current), Some(limit)) => limit <= current,
+    };
+
+    let content = if current.is_none() && valor.is_none() {
+        pretty_message(
+            icon::EMPTY,
+            format!("Você não tem um limite {} para remover.", periodo.label()),
+        )
+    } else if tightens {
+        database::gambling::set_limit_now(&db, user.id, period, valor).await?;
+        pretty_message(
+            icon::CHECK,
+            format!(
+                "Limite {} de perdas definido em {} moedas. Já está valendo.",
+                periodo.label(),
+                bold(format_currency(valor.unwrap_or_default()))
+            ),
+        )
+    } else {
+        let effective_at = now + COOLING_OFF;
+        database::gambling::schedule_limit(&db, user.id, period, valor, effective_at).await?;
+        let change = match valor {
+            Some(limit) => format!(
+                "O novo limite {} de {} moedas",
+                periodo.label(),
+                bold(format_currency(limit))
+            ),
+            None => format!("A remoção do limite {}", periodo.label()),
+        };
+        pretty_message(
+            icon::TIMER,
+            format!(
+                "{change} entra em vigor {}. Até lá, o limite atual continua valendo.",
+                time::describe_relative(effective_at)
+            ),
+        )
+    };
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Faça uma pausa nas apostas por alguns dias.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "autoexclusão",
+    aliases("autoexclusao"),
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn gambling_exclusion(
+    ctx: Context<'_>,
+    #[description = "Dias sem poder apostar"]
+    #[min = 1]
+    #[max = 365]
+    dias: i64,
+) -> Result<(), Error> {
+    let dias = dias.clamp(1, 365);
+    let until = Utc::now() + Duration::days(dias);
+
+    let mut prompt = ConfirmationPromptOptions::new(pretty_message(
+        icon::BELL,
+        format!(
+            "Bloquear suas apostas por {} dia(s), até {}? A pausa não pode ser desfeita.",
+            bold(dias.to_string()),
+            time::describe_absolute(until)
+        ),
+    ));
+    prompt.timeout = CONFIRMATION_TIMEOUT;
+
+    let confirmation = confirmation_prompt(&ctx, ctx.author().id, prompt).await?;
+    if !matches!(confirmation.outcome, ConfirmationOutcome::Accepted) {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(icon::MINUS, "Autoexclusão cancelada."))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let limits = database::gambling::exclude_until(&db, user.id, until).await?;
+    let until = limits.excluded_until_datetime().unwrap_or(until);
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(pretty_message(
+                icon::CHECK,
+                format!(
+                    "Apostas bloqueadas até {}. Cuide-se!",
+                    time::describe_absolute(until)
+                ),
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Veja seus limites e perdas recentes.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "status",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn gambling_status(ctx: Context<'_>) -> Result<(), Error> {
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let now = Utc::now();
+    let limits = database::gambling::get(&db, user.id, now).await?;
+
+    let mut lines = Vec::new();
+    for choice in [PeriodChoice::Daily, PeriodChoice::Weekly] {
+        let period = choice.period();
+        let lost = database::gambling::net_loss_since(&db, user.id, now - window(period)).await?;
+        lines.push(limit_line(choice, &limits, lost));
+    }
+
+    let exclusion = match limits.excluded_until_datetime() {
+        Some(until) if is_excluded(&limits, now) => pretty_message(
+            icon::ERROR,
+            format!("Autoexclusão até {}", time::describe_absolute(until)),
+        ),
+        _ => pretty_message(icon::CHECK, "Sem autoexclusão ativa"),
+    };
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("{} Limites de apostas", icon::GEAR))
+        .colour(colors::MOON)
+        .description(lines.join("\n\n"))
+        .field("Autoexclusão", exclusion, false)
+        .footer(CreateEmbedFooter::new(
+            "Reduções valem na hora; aumentos e remoções esperam 24 horas",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+fn limit_line(choice: PeriodChoice, limits: &GamblingLimitsModel, lost: i64) -> String {
+    let period = choice.period();
+    let title = match choice {
+        PeriodChoice::Daily => "Últimas 24 horas",
+        PeriodChoice::Weekly => "Últimos 7 dias",
+    };
+    let limit_text = match period.current(limits) {
+        Some(limit) => format!(
+            "{} de {} moedas",
+            bold(format_currency(lost)),
+            format_currency(limit)
+        ),
+        None => format!("{} moedas • sem limite", bold(format_currency(lost))),
+    };
+
+    let mut line = pretty_message(icon::DOLLAR, format!("{title}: {limit_text}"));
+    let (pending, pending_at) = match period {
+        LimitPeriod::Daily => (limits.pending_daily_limit, &limits.pending_daily_at),
+        LimitPeriod::Weekly => (limits.pending_weekly_limit, &limits.pending_weekly_at),
+    };
+    if let Some(effective_at) = pending_at
+        .as_deref()
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+    {
+        let change = match pending {
+            Some(limit) => format!("novo limite de {} moedas", format_currency(limit)),
+            None => "remoção do limite".to_string(),
+        };
+        line.push('\n');
+        line.push_str(&pretty_message(
+            icon::TIMER,
+            format!(
+                "Pendente: {change} {}",
+                time::describe_relative(effective_at.with_timezone(&Utc))
+            ),
+        ));
+    }
+    line
+}
@@ -1,57 +1,225 @@
 use rand::Rng;
 use std::fmt;
 
-#[derive(Clone, Copy, Eq, PartialEq)]
-pub enum GameMove {
-    Rock,
-    Paper,
-    Scissors,
+/// A move as shown on the buttons
+pub struct MoveSpec {
+    pub id: &'static str,
+    pub emoji: &'static str,
+    pub name: &'static str,
 }
 
-impl GameMove {
-    pub const ALL: [GameMove; 3] = [GameMove::Rock, GameMove::Paper, GameMove::Scissors];
+/// Moves of a jokenpo variant and who beats whom.
+/// `beats` lists `(winner, loser)` pairs by index into `moves`
+pub struct RuleSet {
+    pub name: &'static str,
+    pub moves: &'static [MoveSpec],
+    pub beats: &'static [(usize, usize)],
+}
+
+const ROCK: MoveSpec = MoveSpec {
+    id: "rock",
+    emoji: "🪨",
+    name: "Pedra",
+};
+const PAPER: MoveSpec = MoveSpec {
+    id: "paper",
+    emoji: "📄",
+    name: "Papel",
+};
+const SCISSORS: MoveSpec = MoveSpec {
+    id: "scissors",
+    emoji: "✂️",
+    name: "Tesoura",
+};
+const LIZARD: MoveSpec = MoveSpec {
+    id: "lizard",
+    emoji: "🦎",
+    name: "Lagarto",
+};
+const SPOCK: MoveSpec = MoveSpec {
+    id: "spock",
+    emoji: "🖖",
+    name: "Spock",
+};
+
+pub static CLASSIC: RuleSet = RuleSet {
+    name: "Clássico",
+    moves: &[ROCK, PAPER, SCISSORS],
+    beats: &[(0, 2), (1, 0), (2, 1)],
+};
+
+pub static LIZARD_SPOCK: RuleSet = RuleSet {
+    name: "Pedra, papel, tesoura, lagarto, Spock",
+    moves: &[ROCK, PAPER, SCISSORS, LIZARD, SPOCK],
+    beats: &[
+        (0, 2),
+        (0, 3),
+        (1, 0),
+        (1, 4),
+        (2, 1),
+        (2, 3),
+        (3, 1),
+        (3, 4),
+        (4, 0),
+        (4, 2),
+    ],
+};
+
+/// Rule set picked when starting a game
+#[derive(Clone, Copy, poise::ChoiceParameter)]
+pub enum RuleVariant {
+    #[name = "Clássico"]
+    Classic,
+    #[name = "Lagarto e Spock"]
+    LizardSpock,
+}
+
+impl RuleVariant {
+    pub fn rules(self) -> &'static RuleSet {
+        let rules = match self {
+            Self::Classic => &CLASSIC,
+            Self::LizardSpock => &LIZARD_SPOCK,
+        };
+        debug_assert!(
+            rules.check_balanced().is_ok(),
+            "{} is unbalanced",
+            rules.name
+        );
+        rules
+    }
+}
 
-    pub fn custom_id(self) -> &'static str {
-        match self {
-            GameMove::Rock => "jkp_rock",
-            GameMove::Paper => "jkp_paper",
-            GameMove::Scissors => "jkp_scissors",
+impl RuleSet {
+    pub fn all_moves(&'static self) -> impl Iterator<Item = GameMove> {
+        (0..self.moves.len()).map(move |index| GameMove { rules: self, index })
+    }
+
+    pub fn move_by_custom_id(&'static self, id: &str) -> Option<GameMove> {
+        self.all_moves().find(|mv| mv.custom_id() == id)
+    }
+
+    pub fn random<R: Rng + ?Sized>(&'static self, rng: &mut R) -> GameMove {
+        GameMove {
+            rules: self,
+            index: rng.random_range(0..self.moves.len()),
         }
     }
 
-    pub fn label(self) -> &'static str {
-        match self {
-            GameMove::Rock => "🪨 Pedra",
-            GameMove::Paper => "📄 Papel",
-            GameMove::Scissors => "✂️ Tesoura",
+    /// A rule set is balanced when every pair of different moves has exactly one winner
+    /// and every move beats as many moves as it loses to
+    pub fn check_balanced(&self) -> Result<(), String> {
+        let count = self.moves.len();
+        if count < 3 || count.is_multiple_of(2) {
+            return Err(format!(
+                "{} needs an odd number of moves (3 or more)",
+                self.name
+            ));
+        }
+
+        for &(winner, loser) in self.beats {
+            if winner >= count || loser >= count || winner == loser {
+                return Err(format!(
+                    "{} has an invalid pair ({winner}, {loser})",
+                    self.name
+                ));
+            }
         }
+
+        for a in 0..count {
+            for b in a + 1..count {
+                let outcomes = self
+                    .beats
+                    .iter()
+                    .filter(|pair| **pair == (a, b) || **pair == (b, a))
+                    .count();
+                if outcomes != 1 {
+                    return Err(format!(
+                        "{}: {} and {} must have exactly one winner",
+                        self.name, self.moves[a].name, self.moves[b].name
+                    ));
+                }
+            }
+
+            let wins = self.beats.iter().filter(|(winner, _)| *winner == a).count();
+            if wins != count / 2 {
+                return Err(format!(
+                    "{}: {} beats {wins} moves instead of {}",
+                    self.name,
+                    self.moves[a].name,
+                    count / 2
+                ));
+            }
+        }
+
+        Ok(())
     }
+}
 
-    pub fn beats(self, other: GameMove) -> bool {
-        matches!(
-            (self, other),
-            (GameMove::Rock, GameMove::Scissors)
-                | (GameMove::Paper, GameMove::Rock)
-                | (GameMove::Scissors, GameMove::Paper)
-        )
+/// A move within its rule set
+#[derive(Clone, Copy)]
+pub struct GameMove {
+    rules: &'static RuleSet,
+    index: usize,
+}
+
+impl GameMove {
+    fn spec(self) -> &'static MoveSpec {
+        &self.rules.moves[self.index]
     }
 
-    pub fn from_custom_id(id: &str) -> Option<Self> {
-        GameMove::ALL.into_iter().find(|mv| mv.custom_id() == id)
+    pub fn custom_id(self) -> String {
+        format!("jkp_{}", self.spec().id)
+    }
+
+    pub fn label(self) -> String {
+        format!("{} {}", self.spec().emoji, self.spec().name)
+    }
+
+    pub fn beats(self, other: GameMove) -> bool {
+        self.rules.beats.contains(&(self.index, other.index))
     }
+}
 
-    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
-        let idx = rng.random_range(0..Self::ALL.len());
-        Self::ALL[idx]
+impl PartialEq for GameMove {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.rules, other.rules) && self.index == other.index
     }
 }
 
+impl Eq for GameMove {}
+
 impl fmt::Display for GameMove {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            GameMove::Rock => write!(f, "🪨 Pedra"),
-            GameMove::Paper => write!(f, "📄 Papel"),
-            GameMove::Scissors => write!(f, "✂️ Tesoura"),
+        f.write_str(&self.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_rule_sets_are_balanced() {
+        for variant in [RuleVariant::Classic, RuleVariant::LizardSpock] {
+            assert_eq!(variant.rules().check_balanced(), Ok(()));
         }
     }
+
+    #[test]
+    fn lopsided_rule_set_is_rejected() {
+        static LOPSIDED: RuleSet = RuleSet {
+            name: "lopsided",
+            moves: &[ROCK, PAPER, SCISSORS],
+            beats: &[(0, 1), (0, 2), (1, 2)],
+        };
+        assert!(LOPSIDED.check_balanced().is_err());
+    }
+
+    #[test]
+    fn spock_vaporizes_rock() {
+        let spock = LIZARD_SPOCK.move_by_custom_id("jkp_spock").unwrap();
+        let rock = LIZARD_SPOCK.move_by_custom_id("jkp_rock").unwrap();
+        assert!(spock.beats(rock));
+        assert!(!rock.beats(spock));
+    }
 }
@@ -1,8 +1,10 @@
 use crate::{
     Context, Error,
     constants::{colors, icon},
+    database,
     functions::{
-        format::pretty_message,
+        bot::gambling,
+        format::{discord::bold, format_currency, pretty_message},
         interactions::{
             component::{send_ephemeral_response, update_component_message},
             opponent::{OpponentValidationMessages, ensure_valid_opponent},
@@ -11,20 +13,30 @@ use crate::{
                 confirmation_prompt,
             },
         },
+        session,
     },
 };
 use poise::serenity_prelude::{self as serenity, Mentionable};
 use serenity::builder::EditMessage;
 use serenity::collector::ComponentInteractionCollector;
 use serenity::{CreateActionRow, CreateButton};
-use std::{collections::HashMap, time::Duration};
+use std::time::Duration;
+use tokio::time::Instant;
 
 mod game_move;
-use game_move::GameMove;
+mod series;
+pub mod tournament;
+mod wager;
+use game_move::{GameMove, RuleSet, RuleVariant};
+use series::{Round, Series, SeriesLength};
+use tournament::tournament;
+use wager::{Escrow, MAX_WAGER, MIN_WAGER};
 
 const SOLO_TIMEOUT: Duration = Duration::from_secs(30);
 const VERSUS_ROUND_TIMEOUT: Duration = Duration::from_secs(60);
 const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(45);
+/// Identifies jokenpo stakes in `game_sessions`
+pub const GAME_NAME: &str = "jokenpo";
 
 /// Jogue pedra, papel ou tesoura!
 #[poise::command(
@@ -32,7 +44,7 @@ const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(45);
     prefix_command,
     interaction_context = "Guild",
     category = "Jogos",
-    subcommands("fumo", "versus"),
+    subcommands("fumo", "versus", "tournament"),
     on_error = "crate::commands::util::command_error_handler"
 )]
 pub async fn jokenpo(_: Context<'_>) -> Result<(), Error> {
@@ -46,7 +58,11 @@ pub async fn jokenpo(_: Context<'_>) -> Result<(), Error> {
     category = "Jogos",
     on_error = "crate::commands::util::command_error_handler"
 )]
-pub async fn fumo(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn fumo(
+    ctx: Context<'_>,
+    #[description = "Regras da partida (padrão: clássico)"] regras: Option<RuleVariant>,
+) -> Result<(), Error> {
+    let rules = regras.unwrap_or(RuleVariant::Classic).rules();
     let intro_embed = serenity::CreateEmbed::new()
         .title("🪨 JoKenPo - Solo")
         .colour(colors::MOON)
@@ -58,7 +74,7 @@ pub async fn fumo(ctx: Context<'_>) -> Result<(), Error> {
         .send(
             poise::CreateReply::default()
                 .embed(intro_embed)
-                .components(action_rows(false, &[])),
+                .components(action_rows(rules, false, &[])),
         )
         .await?;
 
@@ -72,7 +88,7 @@ pub async fn fumo(ctx: Context<'_>) -> Result<(), Error> {
 
     match interaction {
         Some(interaction) => {
-            let Some(user_move) = GameMove::from_custom_id(&interaction.data.custom_id) else {
+            let Some(user_move) = rules.move_by_custom_id(&interaction.data.custom_id) else {
                 let embed = serenity::CreateEmbed::new()
                     .colour(colors::MOON)
                     .description("Jogada desconhecida recebida. Por favor, tente novamente.");
@@ -82,7 +98,7 @@ pub async fn fumo(ctx: Context<'_>) -> Result<(), Error> {
 
             let bot_move = {
                 let mut rng = rand::rng();
-                GameMove::random(&mut rng)
+                rules.random(&mut rng)
             };
 
             let outcome = if user_move == bot_move {
@@ -106,7 +122,7 @@ pub async fn fumo(ctx: Context<'_>) -> Result<(), Error> {
                 &ctx,
                 &interaction,
                 embed,
-                action_rows(true, &[user_move, bot_move]),
+                action_rows(rules, true, &[user_move, bot_move]),
             )
             .await?;
         }
@@ -142,6 +158,9 @@ pub async fn fumo(ctx: Context<'_>) -> Result<(), Error> {
 pub async fn versus(
     ctx: Context<'_>,
     #[description = "Jogador que você deseja desafiar"] opponent: serenity::User,
+    #[description = "Valor que cada jogador aposta"] aposta: Option<i64>,
+    #[description = "Quantidade de rodadas (padrão: rodada única)"] melhor_de: Option<SeriesLength>,
+    #[description = "Regras da partida (padrão: clássico)"] regras: Option<RuleVariant>,
 ) -> Result<(), Error> {
     let validator = OpponentValidationMessages::new(
         "Você precisa convidar outra pessoa para jogar.",
@@ -151,14 +170,74 @@ pub async fn versus(
         return Ok(());
     }
 
-    let mut prompt = ConfirmationPromptOptions::new(pretty_message(
-        icon::BELL,
-        format!(
-            "{} desafiou {} para um JoKenPo. Aceita?",
-            ctx.author().mention(),
-            opponent.mention()
-        ),
-    ));
+    let _challenger_session = match aposta {
+        Some(stake) => {
+            if !(MIN_WAGER..=MAX_WAGER).contains(&stake) {
+                ctx.send(
+                    poise::CreateReply::default()
+                        .content(pretty_message(
+                            icon::ERROR,
+                            format!(
+                                "A aposta deve ficar entre {} e {} moedas.",
+                                bold(format_currency(MIN_WAGER)),
+                                bold(format_currency(MAX_WAGER))
+                            ),
+                        ))
+                        .ephemeral(true),
+                )
+                .await?;
+                return Ok(());
+            }
+
+            let Some(guard) = gambling::claim_wager_session(&ctx).await? else {
+                return Ok(());
+            };
+
+            let db = ctx.data().database.clone();
+            let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+            if user.dollars < stake {
+                ctx.send(
+                    poise::CreateReply::default()
+                        .content(pretty_message(
+                            icon::ERROR,
+                            "Você não possui moedas suficientes para essa aposta.",
+                        ))
+                        .ephemeral(true),
+                )
+                .await?;
+                return Ok(());
+            }
+
+            if !gambling::ensure_can_wager(&ctx, user.id, stake).await? {
+                return Ok(());
+            }
+
+            Some(guard)
+        }
+        None => None,
+    };
+
+    let best_of = melhor_de.map_or(1, SeriesLength::rounds);
+    let rules = regras.unwrap_or(RuleVariant::Classic).rules();
+    let mut invitation = format!(
+        "{} desafiou {} para um JoKenPo",
+        ctx.author().mention(),
+        opponent.mention()
+    );
+    if best_of > 1 {
+        invitation.push_str(&format!(" (melhor de {best_of})"));
+    }
+    if !std::ptr::eq(rules, &game_move::CLASSIC) {
+        invitation.push_str(&format!(" com as regras {}", bold(rules.name)));
+    }
+    if let Some(stake) = aposta {
+        invitation.push_str(&format!(
+            " valendo {} moedas de cada um",
+            bold(format_currency(stake))
+        ));
+    }
+    invitation.push_str(". Aceita?");
+    let mut prompt = ConfirmationPromptOptions::new(pretty_message(icon::BELL, invitation));
     prompt.timeout = CONFIRMATION_TIMEOUT;
     prompt.keep_message_on_accept = true;
 
@@ -166,7 +245,12 @@ pub async fn versus(
 
     match confirmation.outcome {
         ConfirmationOutcome::Accepted => {
-            start_versus_match(ctx, opponent, confirmation.message).await
+            let series = if aposta.is_some() {
+                Series::wagered(rules, best_of)
+            } else {
+                Series::new(rules, best_of)
+            };
+            start_versus_match(ctx, opponent, confirmation.message, aposta, series).await
         }
         ConfirmationOutcome::Declined => {
             ctx.send(
@@ -188,23 +272,79 @@ async fn start_versus_match(
     ctx: Context<'_>,
     opponent: serenity::User,
     existing_message: Option<ConfirmationMessageHandle>,
+    stake: Option<i64>,
+    series: Series,
 ) -> Result<(), Error> {
     let challenger = ctx.author().clone();
 
+    let _opponent_session;
+    let escrow = match stake {
+        Some(stake) => {
+            let Some(guard) = session::wager_sessions().claim(opponent.id) else {
+                let reason = format!("{} já tem uma aposta em andamento.", opponent.mention());
+                return cancel_before_match(&ctx, existing_message, &reason).await;
+            };
+            _opponent_session = guard;
+
+            match open_escrow(&ctx, &challenger, &opponent, stake).await? {
+                Ok(escrow) => Some(escrow),
+                Err(reason) => {
+                    return cancel_before_match(&ctx, existing_message, &reason).await;
+                }
+            }
+        }
+        None => None,
+    };
+
+    let result = play_versus_match(
+        &ctx,
+        &challenger,
+        &opponent,
+        existing_message,
+        escrow.as_ref(),
+        series,
+    )
+    .await;
+    // Stakes are already debited; give them back if the match broke off before settling.
+    // Sessions that were already closed are left as they are
+    if result.is_err()
+        && let Some(escrow) = &escrow
+        && let Err(err) = escrow.refund(&ctx, "cancelled").await
+    {
+        eprintln!("Failed to refund jokenpo stakes after the match failed: {err:?}");
+    }
+    result
+}
+
+/// Runs the match once both players are in, settling `escrow` when there is one
+async fn play_versus_match(
+    ctx: &Context<'_>,
+    challenger: &serenity::User,
+    opponent: &serenity::User,
+    existing_message: Option<ConfirmationMessageHandle>,
+    escrow: Option<&Escrow>,
+    mut series: Series,
+) -> Result<(), Error> {
     let (channel_id, message_id, needs_edit) = if let Some(handle) = existing_message {
         (handle.channel_id, handle.message_id, true)
     } else {
         let reply = ctx
             .send(
                 poise::CreateReply::default()
-                    .embed(versus_waiting_embed(&challenger, &opponent))
-                    .components(action_rows(false, &[])),
+                    .embed(versus_waiting_embed(challenger, opponent, &series))
+                    .components(action_rows(series.rules, false, &[])),
             )
             .await?;
         let message = reply.message().await?;
         (message.channel_id, message.id, false)
     };
 
+    if let Some(escrow) = escrow {
+        escrow
+            .attach_message(ctx, channel_id.get() as i64, message_id.get() as i64)
+            .await?;
+    }
+
     if needs_edit {
         channel_id
             .edit_message(
@@ -212,51 +352,206 @@ async fn start_versus_match(
                 message_id,
                 EditMessage::new()
                     .content("")
-                    .embed(versus_waiting_embed(&challenger, &opponent))
-                    .components(action_rows(false, &[])),
+                    .embed(versus_waiting_embed(challenger, opponent, &series))
+                    .components(action_rows(series.rules, false, &[])),
             )
             .await?;
     }
 
-    let mut selections: HashMap<serenity::UserId, GameMove> = HashMap::new();
+    let players = [challenger, opponent];
+    if !play_series(ctx, channel_id, message_id, players, &mut series).await? {
+        let reason = match escrow {
+            Some(escrow) => {
+                escrow.refund(ctx, "expired").await?;
+                "Partida cancelada por inatividade. As apostas foram devolvidas."
+            }
+            None => "Partida cancelada por inatividade.",
+        };
+        channel_id
+            .edit_message(
+                ctx.serenity_context(),
+                message_id,
+                EditMessage::new()
+                    .content("")
+                    .embed(versus_cancelled_embed(reason))
+                    .components(Vec::new()),
+            )
+            .await?;
+        return Ok(());
+    }
 
-    loop {
-        let interaction = ComponentInteractionCollector::new(ctx.serenity_context())
-            .message_id(message_id)
-            .timeout(VERSUS_ROUND_TIMEOUT)
-            .await;
+    let winner = series.leader();
+    let mut outcome = match winner {
+        None => pretty_message(icon::HASTAG, "Empate!"),
+        Some(seat) if series.best_of > 1 => pretty_message(
+            icon::CHECK,
+            format!("{} venceu a série!", players[seat].mention()),
+        ),
+        Some(seat) => pretty_message(
+            icon::CHECK,
+            format!("{} venceu a rodada!", players[seat].mention()),
+        ),
+    };
 
-        let Some(interaction) = interaction else {
+    if let Some(escrow) = escrow {
+        let settlement = match winner {
+            None => {
+                escrow.refund(ctx, "draw").await?;
+                pretty_message(
+                    icon::DOLLAR,
+                    format!(
+                        "As apostas de {} moedas foram devolvidas.",
+                        bold(format_currency(escrow.stake))
+                    ),
+                )
+            }
+            Some(seat) => match escrow.pay_winner(ctx, seat).await? {
+                Some(payout) => pretty_message(
+                    icon::DOLLAR,
+                    format!(
+                        "{} levou {} moedas (taxa da casa: {} moedas).",
+                        players[seat].mention(),
+                        bold(format_currency(payout.amount)),
+                        format_currency(payout.house_cut)
+                    ),
+                ),
+                None => pretty_message(icon::ERROR, "Essa aposta já foi encerrada."),
+            },
+        };
+        outcome = format!("{outcome}\n{settlement}");
+    }
+
+    channel_id
+        .edit_message(
+            ctx.serenity_context(),
+            message_id,
+            series_result_message(&series, players, outcome),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Plays rounds on the match message until the series is over, refreshing the score
+/// between rounds. Returns `false` when a round abandons the match (see [`Series::record`])
+async fn play_series(
+    ctx: &Context<'_>,
+    channel_id: serenity::ChannelId,
+    message_id: serenity::MessageId,
+    players: [&serenity::User; 2],
+    series: &mut Series,
+) -> Result<bool, Error> {
+    while !series.is_over() {
+        let round = play_round(ctx, message_id, players, series).await?;
+        if !series.record(round) {
+            return Ok(false);
+        }
+
+        if !series.is_over() {
             channel_id
                 .edit_message(
                     ctx.serenity_context(),
                     message_id,
-                    serenity::builder::EditMessage::new()
-                        .content("")
-                        .embed(versus_cancelled_embed("Partida cancelada por inatividade."))
-                        .components(Vec::new()),
+                    EditMessage::new()
+                        .embed(versus_waiting_embed(players[0], players[1], series))
+                        .components(action_rows(series.rules, false, &[])),
                 )
                 .await?;
-            return Ok(());
+        }
+    }
+    Ok(true)
+}
+
+/// Final view of a match: the moves (or series history), followed by `outcome`
+fn series_result_message(
+    series: &Series,
+    players: [&serenity::User; 2],
+    outcome: String,
+) -> EditMessage {
+    let mut lines = if series.best_of > 1 || series.rounds.len() > 1 {
+        vec![
+            score_line(series, players),
+            String::new(),
+            series_history(series, players),
+        ]
+    } else {
+        let last = &series.rounds[series.rounds.len() - 1];
+        players
+            .iter()
+            .zip(last.moves)
+            .map(|(player, chosen)| {
+                pretty_message(
+                    icon::BELL,
+                    format!("{} escolheu {}", player.mention(), move_label(chosen)),
+                )
+            })
+            .collect()
+    };
+    lines.push(String::new());
+    lines.push(outcome);
+
+    let embed = serenity::CreateEmbed::new()
+        .title("🪨 JoKenPo")
+        .colour(colors::MINT)
+        .description(lines.join("\n"));
+
+    let highlights: Vec<GameMove> = series
+        .rounds
+        .last()
+        .map(|round| round.moves.iter().flatten().copied().collect())
+        .unwrap_or_default();
+    EditMessage::new()
+        .content("")
+        .embed(embed)
+        .components(action_rows(series.rules, true, &highlights))
+}
+
+/// Collects both players' moves for one round. Each round gets its own `VERSUS_ROUND_TIMEOUT`;
+/// whoever has not chosen by then is left without a move, and [`Series::record`] decides
+/// whether that forfeits the round or abandons the match
+async fn play_round(
+    ctx: &Context<'_>,
+    message_id: serenity::MessageId,
+    players: [&serenity::User; 2],
+    series: &Series,
+) -> Result<Round, Error> {
+    let deadline = Instant::now() + VERSUS_ROUND_TIMEOUT;
+    let mut moves: [Option<GameMove>; 2] = [None, None];
+
+    while moves.iter().any(Option::is_none) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+
+        let Some(interaction) = ComponentInteractionCollector::new(ctx.serenity_context())
+            .message_id(message_id)
+            .timeout(remaining)
+            .await
+        else {
+            break;
         };
 
-        if interaction.user.id != challenger.id && interaction.user.id != opponent.id {
+        let Some(seat) = players
+            .iter()
+            .position(|player| player.id == interaction.user.id)
+        else {
             send_ephemeral_response(
-                &ctx,
+                ctx,
                 &interaction,
                 pretty_message(icon::ERROR, "Apenas os jogadores podem usar estes botões."),
             )
             .await?;
             continue;
-        }
+        };
 
-        let Some(chosen_move) = GameMove::from_custom_id(&interaction.data.custom_id) else {
+        let Some(chosen_move) = series.rules.move_by_custom_id(&interaction.data.custom_id) else {
             continue;
         };
 
-        if selections.contains_key(&interaction.user.id) {
+        if moves[seat].is_some() {
             send_ephemeral_response(
-                &ctx,
+                ctx,
                 &interaction,
                 pretty_message(icon::ERROR, "Você já escolheu sua jogada."),
             )
@@ -264,105 +559,128 @@ async fn start_versus_match(
             continue;
         }
 
-        selections.insert(interaction.user.id, chosen_move);
+        moves[seat] = Some(chosen_move);
 
         send_ephemeral_response(
-            &ctx,
+            ctx,
             &interaction,
             pretty_message(icon::CHECK, format!("Jogada registrada: {}", chosen_move)),
         )
         .await?;
-
-        if selections.len() == 2 {
-            break;
-        }
     }
 
-    let missing_player = if !selections.contains_key(&challenger.id) {
-        Some(challenger.clone())
-    } else if !selections.contains_key(&opponent.id) {
-        Some(opponent.clone())
-    } else {
-        None
-    };
+    Ok(Round { moves })
+}
 
-    if let Some(missing) = missing_player {
-        let reason = format!(
-            "Partida cancelada: {} não escolheu uma jogada a tempo.",
-            missing.mention()
-        );
-        channel_id
-            .edit_message(
-                ctx.serenity_context(),
-                message_id,
-                serenity::builder::EditMessage::new()
-                    .content("")
-                    .embed(versus_cancelled_embed(&reason))
-                    .components(Vec::new()),
-            )
-            .await?;
-        return Ok(());
+fn move_label(chosen: Option<GameMove>) -> String {
+    match chosen {
+        Some(chosen) => chosen.to_string(),
+        None => "⌛ nada a tempo".to_string(),
     }
+}
 
-    let challenger_move = selections
-        .get(&challenger.id)
-        .copied()
-        .expect("challenger move missing");
-    let opponent_move = selections
-        .get(&opponent.id)
-        .copied()
-        .expect("opponent move missing");
-
-    let outcome = match (challenger_move, opponent_move) {
-        (a, b) if a == b => pretty_message(icon::HASTAG, "Empate!"),
-        (a, b) if a.beats(b) => pretty_message(
-            icon::CHECK,
-            format!("{} venceu a rodada!", challenger.mention()),
-        ),
-        _ => pretty_message(
-            icon::CHECK,
-            format!("{} venceu a rodada!", opponent.mention()),
+fn score_line(series: &Series, players: [&serenity::User; 2]) -> String {
+    let [challenger_wins, opponent_wins] = series.score();
+    pretty_message(
+        icon::HASTAG,
+        format!(
+            "Placar: {} {} x {} {}",
+            players[0].mention(),
+            bold(challenger_wins.to_string()),
+            bold(opponent_wins.to_string()),
+            players[1].mention()
         ),
-    };
+    )
+}
 
-    let embed = serenity::CreateEmbed::new()
-        .title("🪨 JoKenPo")
-        .colour(colors::MINT)
-        .description(
-            [
-                pretty_message(
-                    icon::BELL,
-                    format!("{} escolheu {}", challenger.mention(), challenger_move),
-                ),
-                pretty_message(
-                    icon::BELL,
-                    format!("{} escolheu {}", opponent.mention(), opponent_move),
-                ),
-                String::new(),
-                outcome,
-            ]
-            .join("\n"),
-        );
+fn series_history(series: &Series, players: [&serenity::User; 2]) -> String {
+    series
+        .rounds
+        .iter()
+        .enumerate()
+        .map(|(index, round)| {
+            let result = match round.winner() {
+                Some(seat) => players[seat].mention().to_string(),
+                None => "empate".to_string(),
+            };
+            format!(
+                "Rodada {}: {} x {} — {}",
+                index + 1,
+                move_label(round.moves[0]),
+                move_label(round.moves[1]),
+                result
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
-    channel_id
-        .edit_message(
-            ctx.serenity_context(),
-            message_id,
-            serenity::builder::EditMessage::new()
-                .content("")
-                .embed(embed)
-                .components(action_rows(true, &[challenger_move, opponent_move])),
-        )
-        .await?;
+/// Checks the opponent can bet and escrows both stakes.
+/// The inner `Err` carries the reason shown when the match cannot start
+async fn open_escrow(
+    ctx: &Context<'_>,
+    challenger: &serenity::User,
+    opponent: &serenity::User,
+    stake: i64,
+) -> Result<Result<Escrow, String>, Error> {
+    let db = ctx.data().database.clone();
+    let challenger_user = database::get_or_create_user(&db, challenger.id.get() as i64).await?;
+    let opponent_user = database::get_or_create_user(&db, opponent.id.get() as i64).await?;
+
+    if gambling::check_wager(&db, opponent_user.id, stake)
+        .await?
+        .is_some()
+    {
+        return Ok(Err(format!(
+            "{} não pode participar de apostas no momento.",
+            opponent.mention()
+        )));
+    }
+
+    match Escrow::open(ctx, [challenger_user.id, opponent_user.id], stake).await? {
+        Some(escrow) => Ok(Ok(escrow)),
+        None => Ok(Err(
+            "Um dos jogadores não possui moedas suficientes para essa aposta.".to_string(),
+        )),
+    }
+}
 
+async fn cancel_before_match(
+    ctx: &Context<'_>,
+    existing_message: Option<ConfirmationMessageHandle>,
+    reason: &str,
+) -> Result<(), Error> {
+    match existing_message {
+        Some(handle) => {
+            handle
+                .channel_id
+                .edit_message(
+                    ctx.serenity_context(),
+                    handle.message_id,
+                    EditMessage::new()
+                        .content("")
+                        .embed(versus_cancelled_embed(reason))
+                        .components(Vec::new()),
+                )
+                .await?;
+        }
+        None => {
+            ctx.send(poise::CreateReply::default().embed(versus_cancelled_embed(reason)))
+                .await?;
+        }
+    }
     Ok(())
 }
 
-fn action_rows(disabled: bool, highlights: &[GameMove]) -> Vec<CreateActionRow> {
-    let buttons = GameMove::ALL
-        .into_iter()
+fn action_rows(
+    rules: &'static RuleSet,
+    disabled: bool,
+    highlights: &[GameMove],
+) -> Vec<CreateActionRow> {
+    let buttons = rules
+        .all_moves()
         .map(|mv| {
-            let is_highlighted = highlights.iter().any(|selected| *selected == mv);
+            let is_highlighted = highlights.contains(&mv);
             let style = if is_highlighted {
                 serenity::ButtonStyle::Success
             } else if disabled {
@@ -382,23 +700,45 @@ fn action_rows(disabled: bool, highlights: &[GameMove]) -> Vec<CreateActionRow>
 fn versus_waiting_embed(
     challenger: &serenity::User,
     opponent: &serenity::User,
+    series: &Series,
 ) -> serenity::CreateEmbed {
+    let mut lines = vec![pretty_message(
+        icon::BELL,
+        format!("{} vs {}", challenger.mention(), opponent.mention()),
+    )];
+
+    if series.best_of > 1 || !series.rounds.is_empty() {
+        let round_info = if series.current_round() > series.best_of as usize {
+            format!(
+                "Rodada {} • Desempate: vence quem sair na frente",
+                series.current_round()
+            )
+        } else {
+            format!(
+                "Rodada {} • Melhor de {} (vence quem chegar a {} vitórias)",
+                series.current_round(),
+                series.best_of,
+                series.target()
+            )
+        };
+        lines.push(pretty_message(icon::TIMER, round_info));
+        lines.push(score_line(series, [challenger, opponent]));
+        if !series.rounds.is_empty() {
+            lines.push(String::new());
+            lines.push(series_history(series, [challenger, opponent]));
+            lines.push(String::new());
+        }
+    }
+
+    lines.push(pretty_message(
+        icon::TIMER,
+        "Escolham uma jogada nos botões abaixo. O resultado aparece assim que ambos decidirem.",
+    ));
+
     serenity::CreateEmbed::new()
         .title("🪨 JoKenPo")
         .colour(colors::MOON)
-        .description(
-            vec![
-                pretty_message(
-                    icon::BELL,
-                    format!("{} vs {}", challenger.mention(), opponent.mention()),
-                ),
-                pretty_message(
-                    icon::TIMER,
-                    "Escolham uma jogada nos botões abaixo. O resultado aparece assim que ambos decidirem.",
-                ),
-            ]
-            .join("\n"),
-        )
+        .description(lines.join("\n"))
 }
 
 fn versus_cancelled_embed(reason: &str) -> serenity::CreateEmbed {
@@ -0,0 +1,212 @@
+use super::game_move::{GameMove, RuleSet};
+use std::cmp::Ordering;
+
+/// How many rounds a versus series can last
+#[derive(Clone, Copy, poise::ChoiceParameter)]
+pub enum SeriesLength {
+    #[name = "Melhor de 3"]
+    BestOf3,
+    #[name = "Melhor de 5"]
+    BestOf5,
+    #[name = "Melhor de 7"]
+    BestOf7,
+}
+
+impl SeriesLength {
+    pub fn rounds(self) -> u32 {
+        match self {
+            Self::BestOf3 => 3,
+            Self::BestOf5 => 5,
+            Self::BestOf7 => 7,
+        }
+    }
+}
+
+/// One finished round. A missing move means that player let the round time out
+pub struct Round {
+    /// Challenger's move first, opponent's second
+    pub moves: [Option<GameMove>; 2],
+}
+
+impl Round {
+    /// Seat that took the round (`0` challenger, `1` opponent), or `None` on a draw
+    pub fn winner(&self) -> Option<usize> {
+        match self.moves {
+            [Some(a), Some(b)] if a == b => None,
+            [Some(a), Some(b)] if a.beats(b) => Some(0),
+            [Some(_), Some(_)] => Some(1),
+            [Some(_), None] => Some(0),
+            [None, Some(_)] => Some(1),
+            [None, None] => None,
+        }
+    }
+}
+
+/// Score and move history of a versus series. Drawn rounds count towards `best_of` without scoring
+pub struct Series {
+    pub rules: &'static RuleSet,
+    pub best_of: u32,
+    pub rounds: Vec<Round>,
+    /// Keep playing past `best_of` while the score is tied, for matches that need a winner
+    sudden_death: bool,
+    /// Whether a player who lets a round time out forfeits it, instead of abandoning the series
+    forfeits: bool,
+}
+
+impl Series {
+    pub fn new(rules: &'static RuleSet, best_of: u32) -> Self {
+        Self {
+            rules,
+            best_of,
+            rounds: Vec::new(),
+            sudden_death: false,
+            forfeits: true,
+        }
+    }
+
+    /// A series with money at stake: any timeout abandons it so both stakes are refunded,
+    /// rather than handing the pot to whoever happened to move
+    pub fn wagered(rules: &'static RuleSet, best_of: u32) -> Self {
+        Self {
+            forfeits: false,
+            ..Self::new(rules, best_of)
+        }
+    }
+
+    /// A series that only ends once someone leads
+    pub fn sudden_death(rules: &'static RuleSet, best_of: u32) -> Self {
+        Self {
+            sudden_death: true,
+            ..Self::new(rules, best_of)
+        }
+    }
+
+    /// Adds a finished round. Returns `false` when the round abandons the series instead:
+    /// nobody moved, or someone timed out in a series without forfeits
+    pub fn record(&mut self, round: Round) -> bool {
+        let abandoned = match round.moves {
+            [None, None] => true,
+            [Some(_), Some(_)] => false,
+            _ => !self.forfeits,
+        };
+        if abandoned {
+            return false;
+        }
+        self.rounds.push(round);
+        true
+    }
+
+    /// Round wins needed to take the series
+    pub fn target(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    /// Rounds won by each seat
+    pub fn score(&self) -> [u32; 2] {
+        let mut score = [0, 0];
+        for winner in self.rounds.iter().filter_map(Round::winner) {
+            score[winner] += 1;
+        }
+        score
+    }
+
+    /// Whether no more rounds are played: someone reached the majority or every round was used
+    pub fn is_over(&self) -> bool {
+        let target = self.target();
+        if self.score().iter().any(|wins| *wins >= target) {
+            return true;
+        }
+        self.rounds.len() >= self.best_of as usize
+            && (!self.sudden_death || self.leader().is_some())
+    }
+
+    /// Seat leading the series; once it is over this is the winner. `None` on a tied score
+    pub fn leader(&self) -> Option<usize> {
+        let [challenger, opponent] = self.score();
+        match challenger.cmp(&opponent) {
+            Ordering::Greater => Some(0),
+            Ordering::Less => Some(1),
+            Ordering::Equal => None,
+        }
+    }
+
+    /// Number of the round being played (1-based)
+    pub fn current_round(&self) -> usize {
+        self.rounds.len() + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::jokenpo::game_move::CLASSIC;
+
+    fn pick(name: &str) -> Option<GameMove> {
+        CLASSIC.move_by_custom_id(&format!("jkp_{name}"))
+    }
+
+    fn round(challenger: Option<GameMove>, opponent: Option<GameMove>) -> Round {
+        Round {
+            moves: [challenger, opponent],
+        }
+    }
+
+    #[test]
+    fn series_ends_once_majority_is_reached() {
+        let mut series = Series::new(&CLASSIC, 5);
+        series.rounds.push(round(pick("rock"), pick("scissors")));
+        series.rounds.push(round(pick("paper"), pick("paper")));
+        series.rounds.push(round(pick("paper"), pick("rock")));
+        assert!(!series.is_over());
+
+        series.rounds.push(round(pick("scissors"), None));
+        assert_eq!(series.score(), [3, 0]);
+        assert!(series.is_over());
+        assert_eq!(series.leader(), Some(0));
+    }
+
+    #[test]
+    fn drawn_rounds_can_leave_the_series_tied() {
+        let mut series = Series::new(&CLASSIC, 3);
+        series.rounds.push(round(pick("rock"), pick("paper")));
+        series.rounds.push(round(pick("rock"), pick("scissors")));
+        series.rounds.push(round(pick("rock"), pick("rock")));
+        assert!(series.is_over());
+        assert_eq!(series.leader(), None);
+    }
+
+    #[test]
+    fn timeout_forfeits_the_round_without_a_wager() {
+        let mut series = Series::new(&CLASSIC, 1);
+        assert!(series.record(round(None, pick("rock"))));
+        assert!(series.is_over());
+        assert_eq!(series.leader(), Some(1));
+    }
+
+    #[test]
+    fn wagered_series_is_abandoned_on_one_sided_timeout() {
+        let mut series = Series::wagered(&CLASSIC, 3);
+        assert!(series.record(round(pick("rock"), pick("scissors"))));
+        assert!(!series.record(round(pick("paper"), None)));
+        assert_eq!(series.rounds.len(), 1);
+        assert!(!series.is_over());
+    }
+
+    #[test]
+    fn round_without_moves_abandons_any_series() {
+        let mut series = Series::new(&CLASSIC, 3);
+        assert!(!series.record(round(None, None)));
+        assert!(series.rounds.is_empty());
+    }
+
+    #[test]
+    fn sudden_death_plays_until_someone_leads() {
+        let mut series = Series::sudden_death(&CLASSIC, 1);
+        series.rounds.push(round(pick("rock"), pick("rock")));
+        assert!(!series.is_over());
+
+        series.rounds.push(round(None, pick("paper")));
+        assert!(series.is_over());
+        assert_eq!(series.leader(), Some(1));
+    }
+}
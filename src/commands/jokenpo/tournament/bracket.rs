@@ -0,0 +1,122 @@
+/// One pairing of the bracket. Slots hold participant indexes; an empty second slot is a bye
+pub struct BracketMatch {
+    pub slots: [Option<usize>; 2],
+    pub winner: Option<usize>,
+}
+
+impl BracketMatch {
+    pub fn is_bye(&self) -> bool {
+        self.slots[1].is_none()
+    }
+}
+
+/// Single-elimination bracket over participants `0..players`, in seed order
+pub struct Bracket {
+    pub rounds: Vec<Vec<BracketMatch>>,
+}
+
+impl Bracket {
+    /// Pairs the first seed with the last, the second with the second to last and so on,
+    /// over a field padded to the next power of two. The padding becomes byes for the top
+    /// seeds, which advance straight away
+    pub fn seeded(players: usize) -> Self {
+        let size = players.next_power_of_two();
+        let first_round = (0..size / 2)
+            .map(|seed| {
+                let rival = size - 1 - seed;
+                let slots = [Some(seed), (rival < players).then_some(rival)];
+                BracketMatch {
+                    slots,
+                    winner: if slots[1].is_none() { Some(seed) } else { None },
+                }
+            })
+            .collect();
+
+        Self {
+            rounds: vec![first_round],
+        }
+    }
+
+    /// Matches of the round being played
+    pub fn current(&self) -> &[BracketMatch] {
+        &self.rounds[self.rounds.len() - 1]
+    }
+
+    /// Records the winner of a match in the current round
+    pub fn record(&mut self, match_index: usize, winner: usize) {
+        let round = self.rounds.len() - 1;
+        self.rounds[round][match_index].winner = Some(winner);
+    }
+
+    /// Moves the winners of a finished round into the next one.
+    /// Returns `false` when the round is still being played or there is nothing left to play
+    pub fn advance(&mut self) -> bool {
+        if !self.round_finished() || self.champion().is_some() {
+            return false;
+        }
+
+        let winners: Vec<Option<usize>> = self.current().iter().map(|m| m.winner).collect();
+        let next_round = winners
+            .chunks(2)
+            .map(|pair| BracketMatch {
+                slots: [pair[0], pair[1]],
+                winner: None,
+            })
+            .collect();
+        self.rounds.push(next_round);
+        true
+    }
+
+    /// Whether every match of the current round has a winner
+    pub fn round_finished(&self) -> bool {
+        self.current().iter().all(|m| m.winner.is_some())
+    }
+
+    /// Winner of the final, once it has been played
+    pub fn champion(&self) -> Option<usize> {
+        match self.current() {
+            [last] => last.winner,
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_seeds_get_the_byes() {
+        let bracket = Bracket::seeded(6);
+        let pairings: Vec<_> = bracket.current().iter().map(|m| m.slots).collect();
+        assert_eq!(
+            pairings,
+            [
+                [Some(0), None],
+                [Some(1), None],
+                [Some(2), Some(5)],
+                [Some(3), Some(4)],
+            ]
+        );
+        assert_eq!(bracket.current()[0].winner, Some(0));
+        assert!(!bracket.round_finished());
+    }
+
+    #[test]
+    fn winners_advance_until_a_champion() {
+        let mut bracket = Bracket::seeded(5);
+        bracket.record(3, 4);
+        assert!(bracket.round_finished());
+
+        assert!(bracket.advance());
+        assert_eq!(bracket.current()[1].slots, [Some(2), Some(4)]);
+        bracket.record(0, 1);
+        bracket.record(1, 4);
+
+        assert!(bracket.advance());
+        assert_eq!(bracket.current()[0].slots, [Some(1), Some(4)]);
+        bracket.record(0, 4);
+        assert_eq!(bracket.champion(), Some(4));
+        assert!(!bracket.advance());
+    }
+}
@@ -0,0 +1,262 @@
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database,
+    functions::{
+        bot::gambling,
+        format::{discord::bold, format_currency, pretty_message},
+        interactions::component::{send_ephemeral_response, update_component_message},
+    },
+};
+use poise::serenity_prelude::{self as serenity, Mentionable};
+use serenity::collector::ComponentInteractionCollector;
+use serenity::{CreateActionRow, CreateButton};
+use std::time::Duration;
+
+const JOIN_BUTTON_ID: &str = "jkp_tournament_join";
+const LEAVE_BUTTON_ID: &str = "jkp_tournament_leave";
+const START_BUTTON_ID: &str = "jkp_tournament_start";
+const CANCEL_BUTTON_ID: &str = "jkp_tournament_cancel";
+
+pub struct LobbyMessageHandle {
+    pub channel_id: serenity::ChannelId,
+    pub message_id: serenity::MessageId,
+}
+
+pub enum LobbyOutcome {
+    Started { players: Vec<serenity::User> },
+    Cancelled,
+    Timeout,
+}
+
+pub struct TournamentLobby {
+    host: serenity::User,
+    players: Vec<serenity::User>,
+    min_players: usize,
+    max_players: usize,
+    entry_fee: Option<i64>,
+}
+
+impl TournamentLobby {
+    pub fn new(
+        host: serenity::User,
+        min_players: usize,
+        max_players: usize,
+        entry_fee: Option<i64>,
+    ) -> Self {
+        Self {
+            host,
+            players: Vec::new(),
+            min_players,
+            max_players,
+            entry_fee,
+        }
+    }
+
+    pub fn render_view(&self) -> (serenity::CreateEmbed, Vec<CreateActionRow>) {
+        let mut description = vec![pretty_message(
+            icon::BELL,
+            format!(
+                "Clique em participar para entrar no torneio. São necessários de {} a {} jogadores.",
+                self.min_players, self.max_players
+            ),
+        )];
+        if let Some(fee) = self.entry_fee {
+            description.push(pretty_message(
+                icon::DOLLAR,
+                format!(
+                    "Inscrição: {} moedas. O campeão leva o prêmio acumulado ({} moedas até agora).",
+                    bold(format_currency(fee)),
+                    format_currency(fee * self.players.len() as i64)
+                ),
+            ));
+        }
+
+        let embed = serenity::CreateEmbed::new()
+            .title("🏆 Torneio de JoKenPo")
+            .colour(colors::MOON)
+            .description(description.join("\n"))
+            .field(
+                format!(
+                    "Participantes ({}/{})",
+                    self.players.len(),
+                    self.max_players
+                ),
+                self.player_list(),
+                false,
+            )
+            .footer(serenity::CreateEmbedFooter::new(format!(
+                "Host: {}",
+                self.host.name.clone()
+            )));
+
+        (embed, self.build_components())
+    }
+
+    pub async fn wait_for_start(
+        &mut self,
+        ctx: &Context<'_>,
+        handle: &LobbyMessageHandle,
+        timeout: Duration,
+    ) -> Result<LobbyOutcome, Error> {
+        loop {
+            let collector = ComponentInteractionCollector::new(ctx.serenity_context())
+                .message_id(handle.message_id)
+                .timeout(timeout);
+            let Some(interaction) = collector.await else {
+                let _ = handle
+                    .channel_id
+                    .delete_message(ctx.serenity_context(), handle.message_id)
+                    .await;
+                return Ok(LobbyOutcome::Timeout);
+            };
+
+            match interaction.data.custom_id.as_str() {
+                JOIN_BUTTON_ID => self.handle_join(ctx, &interaction).await?,
+                LEAVE_BUTTON_ID => self.handle_leave(ctx, &interaction).await?,
+                START_BUTTON_ID => {
+                    if interaction.user.id != self.host.id {
+                        continue;
+                    }
+
+                    if self.players.len() < self.min_players {
+                        send_ephemeral_response(
+                            ctx,
+                            &interaction,
+                            pretty_message(
+                                icon::ERROR,
+                                format!(
+                                    "São necessários pelo menos {} participantes.",
+                                    self.min_players
+                                ),
+                            ),
+                        )
+                        .await?;
+                        continue;
+                    }
+
+                    let embed = serenity::CreateEmbed::new()
+                        .title("🏆 Torneio começando!")
+                        .colour(colors::MINT)
+                        .field("Participantes", self.player_list(), false);
+                    update_component_message(ctx, &interaction, embed, Vec::new()).await?;
+                    return Ok(LobbyOutcome::Started {
+                        players: self.players.clone(),
+                    });
+                }
+                CANCEL_BUTTON_ID => {
+                    if interaction.user.id != self.host.id {
+                        continue;
+                    }
+
+                    let embed = serenity::CreateEmbed::new()
+                        .colour(colors::MOON)
+                        .description(pretty_message(
+                            icon::ERROR,
+                            "O torneio foi cancelado pelo autor.",
+                        ));
+                    update_component_message(ctx, &interaction, embed, Vec::new()).await?;
+                    return Ok(LobbyOutcome::Cancelled);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn player_list(&self) -> String {
+        if self.players.is_empty() {
+            return "Ninguém entrou ainda.".into();
+        }
+
+        self.players
+            .iter()
+            .enumerate()
+            .map(|(idx, player)| format!("{}. {}", idx + 1, player.mention()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn build_components(&self) -> Vec<CreateActionRow> {
+        let join = CreateButton::new(JOIN_BUTTON_ID)
+            .label("Participar")
+            .style(serenity::ButtonStyle::Primary)
+            .disabled(self.players.len() >= self.max_players);
+        let leave = CreateButton::new(LEAVE_BUTTON_ID)
+            .label("Sair")
+            .style(serenity::ButtonStyle::Secondary);
+        let start = CreateButton::new(START_BUTTON_ID)
+            .label("Iniciar")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(self.players.len() < self.min_players)
+            .emoji(icon::CHECK.as_reaction());
+        let cancel = CreateButton::new(CANCEL_BUTTON_ID)
+            .label("Cancelar")
+            .style(serenity::ButtonStyle::Danger)
+            .emoji(icon::ERROR.as_reaction());
+
+        vec![CreateActionRow::Buttons(vec![join, leave, start, cancel])]
+    }
+
+    async fn handle_join(
+        &mut self,
+        ctx: &Context<'_>,
+        interaction: &serenity::ComponentInteraction,
+    ) -> Result<(), Error> {
+        let user = &interaction.user;
+        if user.bot
+            || self.players.len() >= self.max_players
+            || self.players.iter().any(|player| player.id == user.id)
+        {
+            return Ok(());
+        }
+
+        if let Some(fee) = self.entry_fee
+            && let Some(reason) = entry_fee_problem(ctx, user, fee).await?
+        {
+            return send_ephemeral_response(ctx, interaction, pretty_message(icon::ERROR, reason))
+                .await;
+        }
+
+        self.players.push(user.clone());
+        let (embed, components) = self.render_view();
+        update_component_message(ctx, interaction, embed, components).await
+    }
+
+    async fn handle_leave(
+        &mut self,
+        ctx: &Context<'_>,
+        interaction: &serenity::ComponentInteraction,
+    ) -> Result<(), Error> {
+        let Some(index) = self
+            .players
+            .iter()
+            .position(|player| player.id == interaction.user.id)
+        else {
+            return Ok(());
+        };
+
+        self.players.remove(index);
+        let (embed, components) = self.render_view();
+        update_component_message(ctx, interaction, embed, components).await
+    }
+}
+
+/// Why `user` cannot pay the entry fee right now, if anything stops them.
+/// The fee itself is only taken when the tournament starts
+async fn entry_fee_problem(
+    ctx: &Context<'_>,
+    user: &serenity::User,
+    fee: i64,
+) -> Result<Option<&'static str>, Error> {
+    let db = ctx.data().database.clone();
+    let account = database::get_or_create_user(&db, user.id.get() as i64).await?;
+    if account.dollars < fee {
+        return Ok(Some("Você não possui moedas suficientes para a inscrição."));
+    }
+
+    if gambling::check_wager(&db, account.id, fee).await?.is_some() {
+        return Ok(Some("Você não pode participar de apostas no momento."));
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,508 @@
+use super::{
+    action_rows,
+    game_move::CLASSIC,
+    play_series,
+    series::{Series, SeriesLength},
+    series_result_message, versus_cancelled_embed, versus_waiting_embed,
+    wager::{MAX_WAGER, MIN_WAGER},
+};
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{self, Currency, WalletChange, game_session::SessionClose},
+    functions::{
+        format::{discord::bold, format_currency, pretty_message},
+        session::{self, SessionGuard, SessionRegistry},
+    },
+};
+use bracket::{Bracket, BracketMatch};
+use lobby::{LobbyMessageHandle, LobbyOutcome, TournamentLobby};
+use poise::serenity_prelude::{self as serenity, Mentionable};
+use rand::{Rng, seq::SliceRandom};
+use serenity::builder::{CreateMessage, EditMessage};
+use std::{sync::OnceLock, time::Duration};
+use tokio::time::sleep;
+
+mod bracket;
+mod lobby;
+
+const MIN_PLAYERS: usize = 4;
+const MAX_PLAYERS: usize = 16;
+const LOBBY_TIMEOUT: Duration = Duration::from_secs(180);
+const MATCH_DELAY: Duration = Duration::from_secs(3);
+/// Identifies entry fees held in `game_sessions`
+pub const GAME_NAME: &str = "jokenpo_tournament";
+
+/// A channel (and its entrants' wager sessions) stays claimed for at most this long,
+/// even if a tournament never finishes
+const CHANNEL_MAX_HOLD: Duration = Duration::from_secs(2 * 60 * 60);
+
+static ACTIVE_CHANNELS: OnceLock<SessionRegistry<serenity::ChannelId>> = OnceLock::new();
+
+/// Organize um torneio eliminatório de JoKenPo.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "torneio",
+    category = "Jogos",
+    on_error = "crate::commands::util::command_error_handler"
+)]
+pub async fn tournament(
+    ctx: Context<'_>,
+    #[description = "Valor da inscrição; o campeão leva tudo"] inscricao: Option<i64>,
+    #[description = "Rodadas de cada partida (padrão: rodada única)"] melhor_de: Option<
+        SeriesLength,
+    >,
+) -> Result<(), Error> {
+    if let Some(fee) = inscricao
+        && !(MIN_WAGER..=MAX_WAGER).contains(&fee)
+    {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(
+                    icon::ERROR,
+                    format!(
+                        "A inscrição deve ficar entre {} e {} moedas.",
+                        bold(format_currency(MIN_WAGER)),
+                        bold(format_currency(MAX_WAGER))
+                    ),
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some(_channel_guard) = ACTIVE_CHANNELS
+        .get_or_init(|| SessionRegistry::new(CHANNEL_MAX_HOLD))
+        .claim(ctx.channel_id())
+    else {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(
+                    icon::ERROR,
+                    "Já existe um torneio acontecendo neste canal. Aguarde terminar.",
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let mut lobby = TournamentLobby::new(ctx.author().clone(), MIN_PLAYERS, MAX_PLAYERS, inscricao);
+    let (embed, components) = lobby.render_view();
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(embed)
+                .components(components),
+        )
+        .await?;
+    let message = reply.message().await?;
+    let handle = LobbyMessageHandle {
+        channel_id: message.channel_id,
+        message_id: message.id,
+    };
+
+    match lobby.wait_for_start(&ctx, &handle, LOBBY_TIMEOUT).await? {
+        LobbyOutcome::Started { players } => {
+            let best_of = melhor_de.map_or(1, SeriesLength::rounds);
+            run_tournament(ctx, players, handle, inscricao, best_of).await
+        }
+        LobbyOutcome::Cancelled | LobbyOutcome::Timeout => Ok(()),
+    }
+}
+
+/// Entry fees held while the tournament runs, one session per player in seed order
+struct PrizePool {
+    fee: i64,
+    session_ids: Vec<i32>,
+    /// Keeps every entrant out of other wagers until the pool is paid out or refunded
+    _wager_sessions: Vec<SessionGuard<serenity::UserId>>,
+}
+
+impl PrizePool {
+    fn total(&self) -> i64 {
+        self.fee * self.session_ids.len() as i64
+    }
+
+    /// Returns every entry fee still held, closing the entries with `status`
+    async fn refund(&self, ctx: &Context<'_>, status: &str) -> Result<(), Error> {
+        for session_id in &self.session_ids {
+            database::game_session::close_with_refund(
+                &ctx.data().database,
+                *session_id,
+                status,
+                "jokenpo_tournament_refund",
+                "Reembolso do torneio de JoKenPo",
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+async fn run_tournament(
+    ctx: Context<'_>,
+    mut players: Vec<serenity::User>,
+    bracket_message: LobbyMessageHandle,
+    entry_fee: Option<i64>,
+    best_of: u32,
+) -> Result<(), Error> {
+    players.shuffle(&mut rand::rng());
+    let LobbyMessageHandle {
+        channel_id,
+        message_id,
+    } = bracket_message;
+
+    let prize_pool = match entry_fee {
+        Some(fee) => match collect_entry_fees(&ctx, &players, fee).await? {
+            Ok(pool) => Some(pool),
+            Err(reason) => {
+                channel_id
+                    .edit_message(
+                        ctx.serenity_context(),
+                        message_id,
+                        EditMessage::new().embed(versus_cancelled_embed(&format!(
+                            "Torneio cancelado: {reason}"
+                        ))),
+                    )
+                    .await?;
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+
+    let result = play_tournament(
+        &ctx,
+        &players,
+        channel_id,
+        message_id,
+        prize_pool.as_ref(),
+        best_of,
+    )
+    .await;
+    // Fees are already taken; give back whatever was not paid out if the tournament broke off
+    if result.is_err()
+        && let Some(pool) = &prize_pool
+        && let Err(err) = pool.refund(&ctx, "cancelled").await
+    {
+        eprintln!(
+            "Failed to refund jokenpo tournament entry fees after the bracket failed: {err:?}"
+        );
+    }
+    result
+}
+
+/// Plays the bracket to the end and pays the champion from `prize_pool`, if there is one
+async fn play_tournament(
+    ctx: &Context<'_>,
+    players: &[serenity::User],
+    channel_id: serenity::ChannelId,
+    message_id: serenity::MessageId,
+    prize_pool: Option<&PrizePool>,
+    best_of: u32,
+) -> Result<(), Error> {
+    if let Some(pool) = prize_pool {
+        for session_id in &pool.session_ids {
+            database::game_session::attach_message(
+                &ctx.data().database,
+                *session_id,
+                channel_id.get() as i64,
+                message_id.get() as i64,
+            )
+            .await?;
+        }
+    }
+
+    let mut bracket = Bracket::seeded(players.len());
+    let refresh_bracket =
+        |bracket: &Bracket| EditMessage::new().embed(bracket_embed(bracket, players, prize_pool));
+    channel_id
+        .edit_message(
+            ctx.serenity_context(),
+            message_id,
+            refresh_bracket(&bracket),
+        )
+        .await?;
+
+    loop {
+        let stage = round_name(bracket.current().len());
+        let pending: Vec<(usize, [usize; 2])> = bracket
+            .current()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, m)| match (m.slots, m.winner) {
+                ([Some(a), Some(b)], None) => Some((index, [a, b])),
+                _ => None,
+            })
+            .collect();
+
+        for (match_index, seats) in pending {
+            sleep(MATCH_DELAY).await;
+            let match_players = [&players[seats[0]], &players[seats[1]]];
+            let winner = play_match(ctx, channel_id, stage, match_players, best_of).await?;
+            bracket.record(match_index, seats[winner]);
+        }
+
+        channel_id
+            .edit_message(
+                ctx.serenity_context(),
+                message_id,
+                refresh_bracket(&bracket),
+            )
+            .await?;
+        if !bracket.advance() {
+            break;
+        }
+    }
+
+    let Some(champion_seat) = bracket.champion() else {
+        if let Some(pool) = prize_pool {
+            pool.refund(ctx, "cancelled").await?;
+        }
+        return Ok(());
+    };
+    let champion = &players[champion_seat];
+
+    let mut announcement = vec![pretty_message(
+        icon::CHECK,
+        format!("{} é o campeão do torneio de JoKenPo!", champion.mention()),
+    )];
+    if let Some(pool) = prize_pool
+        && pay_champion(ctx, pool, champion_seat).await?
+    {
+        announcement.push(pretty_message(
+            icon::DOLLAR,
+            format!(
+                "Prêmio de {} moedas enviado para a carteira.",
+                bold(format_currency(pool.total()))
+            ),
+        ));
+    }
+
+    channel_id
+        .send_message(
+            ctx.serenity_context(),
+            CreateMessage::new()
+                .embed(
+                    serenity::CreateEmbed::new()
+                        .title("🏆 Torneio encerrado")
+                        .colour(colors::MINT)
+                        .description(announcement.join("\n")),
+                )
+                .reference_message((channel_id, message_id)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Plays one bracket match in its own message and returns the winning seat.
+/// Tied series go to sudden death; if both players stop answering, a coin flip decides
+async fn play_match(
+    ctx: &Context<'_>,
+    channel_id: serenity::ChannelId,
+    stage: &str,
+    players: [&serenity::User; 2],
+    best_of: u32,
+) -> Result<usize, Error> {
+    let mut series = Series::sudden_death(&CLASSIC, best_of);
+    let message = channel_id
+        .send_message(
+            ctx.serenity_context(),
+            CreateMessage::new()
+                .content(format!(
+                    "{stage}: {} vs {}",
+                    players[0].mention(),
+                    players[1].mention()
+                ))
+                .embed(versus_waiting_embed(players[0], players[1], &series))
+                .components(action_rows(series.rules, false, &[])),
+        )
+        .await?;
+
+    if play_series(ctx, channel_id, message.id, players, &mut series).await? {
+        let winner = series.leader().unwrap_or_default();
+        let outcome = pretty_message(
+            icon::CHECK,
+            format!("{} avança no torneio!", players[winner].mention()),
+        );
+        channel_id
+            .edit_message(
+                ctx.serenity_context(),
+                message.id,
+                series_result_message(&series, players, outcome),
+            )
+            .await?;
+        return Ok(winner);
+    }
+
+    let winner = rand::rng().random_range(0..2);
+    let reason = format!(
+        "Ninguém jogou a tempo. {} avança por sorteio.",
+        players[winner].mention()
+    );
+    channel_id
+        .edit_message(
+            ctx.serenity_context(),
+            message.id,
+            EditMessage::new()
+                .embed(versus_cancelled_embed(&reason))
+                .components(Vec::new()),
+        )
+        .await?;
+    Ok(winner)
+}
+
+/// Claims every player's wager session and takes their entry fees at once.
+/// Returns why the tournament cannot go on when someone is busy or can no longer pay
+async fn collect_entry_fees(
+    ctx: &Context<'_>,
+    players: &[serenity::User],
+    fee: i64,
+) -> Result<Result<PrizePool, String>, Error> {
+    let mut wager_sessions = Vec::with_capacity(players.len());
+    for player in players {
+        let Some(guard) = session::wager_sessions().claim_for(player.id, CHANNEL_MAX_HOLD) else {
+            return Ok(Err(format!(
+                "{} já tem uma aposta em andamento.",
+                player.mention()
+            )));
+        };
+        wager_sessions.push(guard);
+    }
+
+    let db = ctx.data().database.clone();
+    let mut stakes = Vec::with_capacity(players.len());
+    for player in players {
+        let user = database::get_or_create_user(&db, player.id.get() as i64).await?;
+        stakes.push((
+            user.id,
+            WalletChange::new(Currency::Dollars, -fee, "jokenpo_tournament_entry")
+                .with_context("Inscrição no torneio de JoKenPo"),
+        ));
+    }
+
+    let Some(session_ids) = database::game_session::start_group(&db, GAME_NAME, &stakes).await?
+    else {
+        return Ok(Err(
+            "alguém não tinha mais moedas para a inscrição.".to_string()
+        ));
+    };
+
+    Ok(Ok(PrizePool {
+        fee,
+        session_ids,
+        _wager_sessions: wager_sessions,
+    }))
+}
+
+/// Pays the whole pool to the champion and closes every entry.
+/// Returns `false` when the entries were already settled
+async fn pay_champion(ctx: &Context<'_>, pool: &PrizePool, seat: usize) -> Result<bool, Error> {
+    let closes: Vec<SessionClose> = pool
+        .session_ids
+        .iter()
+        .enumerate()
+        .map(|(index, session_id)| {
+            if index == seat {
+                SessionClose {
+                    session_id: *session_id,
+                    status: "won",
+                    payout: Some(
+                        WalletChange::new(
+                            Currency::Dollars,
+                            pool.total(),
+                            "jokenpo_tournament_prize",
+                        )
+                        .with_context("Prêmio do torneio de JoKenPo"),
+                    ),
+                }
+            } else {
+                SessionClose {
+                    session_id: *session_id,
+                    status: "lost",
+                    payout: None,
+                }
+            }
+        })
+        .collect();
+
+    let settled = database::game_session::close_group(&ctx.data().database, &closes).await?;
+    Ok(settled.is_some())
+}
+
+fn bracket_embed(
+    bracket: &Bracket,
+    players: &[serenity::User],
+    prize_pool: Option<&PrizePool>,
+) -> serenity::CreateEmbed {
+    let mut description = Vec::new();
+    if let Some(pool) = prize_pool {
+        description.push(pretty_message(
+            icon::DOLLAR,
+            format!(
+                "Prêmio acumulado: {} moedas",
+                bold(format_currency(pool.total()))
+            ),
+        ));
+    }
+    match bracket.champion() {
+        Some(champion) => description.push(pretty_message(
+            icon::CHECK,
+            format!("Campeão: {}", players[champion].mention()),
+        )),
+        None => description.push(pretty_message(
+            icon::TIMER,
+            "As partidas acontecem uma de cada vez neste canal.",
+        )),
+    }
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title("🏆 Chaveamento do torneio")
+        .colour(colors::MOON)
+        .description(description.join("\n"));
+
+    for round in &bracket.rounds {
+        let lines = round
+            .iter()
+            .map(|m| match_line(m, players))
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field(round_name(round.len()), lines, false);
+    }
+
+    embed
+}
+
+fn match_line(bracket_match: &BracketMatch, players: &[serenity::User]) -> String {
+    let name = |slot: Option<usize>| match slot {
+        Some(index) => players[index].mention().to_string(),
+        None => "a definir".to_string(),
+    };
+
+    if bracket_match.is_bye() {
+        return format!("{} avança direto", name(bracket_match.slots[0]));
+    }
+
+    let pairing = format!(
+        "{} vs {}",
+        name(bracket_match.slots[0]),
+        name(bracket_match.slots[1])
+    );
+    match bracket_match.winner {
+        Some(winner) => format!("{pairing} — venceu {}", players[winner].mention()),
+        None => pairing,
+    }
+}
+
+fn round_name(matches: usize) -> &'static str {
+    match matches {
+        1 => "Final",
+        2 => "Semifinal",
+        4 => "Quartas de final",
+        _ => "Oitavas de final",
+    }
+}
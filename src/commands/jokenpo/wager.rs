@@ -0,0 +1,115 @@
+use super::GAME_NAME;
+use crate::{
+    Context, Error,
+    database::{self, Currency, WalletChange, game_session::SessionClose},
+};
+
+pub(super) const MIN_WAGER: i64 = 50;
+pub(super) const MAX_WAGER: i64 = 50_000;
+
+/// Both players' stakes, held in `game_sessions` until the match is settled
+pub(super) struct Escrow {
+    pub stake: i64,
+    /// Challenger's session first, opponent's second
+    session_ids: [i32; 2],
+}
+
+/// How the pot was split after a win
+pub(super) struct Payout {
+    pub amount: i64,
+    pub house_cut: i64,
+}
+
+impl Escrow {
+    /// Debits `stake` from both players at once. Returns `None` when either cannot cover it
+    pub async fn open(
+        ctx: &Context<'_>,
+        user_ids: [i32; 2],
+        stake: i64,
+    ) -> Result<Option<Self>, Error> {
+        let db = ctx.data().database.clone();
+        let stakes = user_ids.map(|user_id| {
+            (
+                user_id,
+                WalletChange::new(Currency::Dollars, -stake, "jokenpo_wager")
+                    .with_context("Aposta no JoKenPo"),
+            )
+        });
+
+        let Some(session_ids) =
+            database::game_session::start_group(&db, GAME_NAME, &stakes).await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            stake,
+            session_ids: [session_ids[0], session_ids[1]],
+        }))
+    }
+
+    /// Records the match message so an interrupted match can be reported on after a restart
+    pub async fn attach_message(
+        &self,
+        ctx: &Context<'_>,
+        channel_id: i64,
+        message_id: i64,
+    ) -> Result<(), Error> {
+        let db = ctx.data().database.clone();
+        for session_id in self.session_ids {
+            database::game_session::attach_message(&db, session_id, channel_id, message_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns both stakes, closing the sessions with `status`
+    pub async fn refund(&self, ctx: &Context<'_>, status: &str) -> Result<(), Error> {
+        let db = ctx.data().database.clone();
+        for session_id in self.session_ids {
+            database::game_session::close_with_refund(
+                &db,
+                session_id,
+                status,
+                "jokenpo_refund",
+                "Reembolso do JoKenPo",
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Pays the pot minus the house cut to the winner (`0` for the challenger, `1` for the
+    /// opponent). Returns `None` when the match was already settled
+    pub async fn pay_winner(
+        &self,
+        ctx: &Context<'_>,
+        winner: usize,
+    ) -> Result<Option<Payout>, Error> {
+        let db = ctx.data().database.clone();
+        let pot = self.stake * 2;
+        let house_cut = pot * ctx.data().economy.jokenpo_house_cut_percent / 100;
+        let amount = pot - house_cut;
+
+        let closes = [0, 1].map(|seat| {
+            if seat == winner {
+                SessionClose {
+                    session_id: self.session_ids[seat],
+                    status: "won",
+                    payout: Some(
+                        WalletChange::new(Currency::Dollars, amount, "jokenpo_payout")
+                            .with_context("Prêmio do JoKenPo"),
+                    ),
+                }
+            } else {
+                SessionClose {
+                    session_id: self.session_ids[seat],
+                    status: "lost",
+                    payout: None,
+                }
+            }
+        });
+
+        let settled = database::game_session::close_group(&db, &closes).await?;
+        Ok(settled.map(|_| Payout { amount, house_cut }))
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{self, LevelEntryModel},
+    functions::{
+        format::{
+            discord::{bold, mention},
+            format_currency, pretty_message,
+        },
+        interactions::pagination::paginate,
+        levels,
+    },
+};
+use poise::serenity_prelude as serenity;
+use serenity::builder::{CreateEmbedAuthor, CreateEmbedFooter};
+use std::time::Duration;
+
+const LEVEL_RANKING_FETCH_LIMIT: i64 = 100;
+const LEVEL_RANKING_PAGE_SIZE: usize = 10;
+const LEVEL_RANKING_TIMEOUT: Duration = Duration::from_secs(180);
+const PROGRESS_BAR_WIDTH: usize = 12;
+
+/// Níveis ganhos conversando no servidor.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "nivel",
+    aliases("nível", "level"),
+    category = "Economia",
+    interaction_context = "Guild",
+    on_error = "crate::commands::util::command_error_handler",
+    subcommands("level_show", "level_ranking")
+)]
+pub async fn level(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Veja o nível de alguém neste servidor.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "ver",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler"
+)]
+pub async fn level_show(
+    ctx: Context<'_>,
+    #[description = "Ver o nível de outra pessoa"] usuario: Option<serenity::User>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let target = usuario.as_ref().unwrap_or_else(|| ctx.author());
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, target.id.get() as i64).await?;
+    let entry = database::level::find_position(&db, guild_id.get() as i64, user.id).await?;
+
+    let Some(entry) = entry else {
+        let message = if target.id == ctx.author().id {
+            "Você ainda não ganhou XP neste servidor. Converse um pouco!".to_string()
+        } else {
+            format!("{} ainda não ganhou XP neste servidor.", target.name)
+        };
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(icon::EMPTY, message))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let progress = levels::progress_for(entry.xp);
+    let embed = serenity::CreateEmbed::new()
+        .author(CreateEmbedAuthor::new(format!("Nível de {}", target.name)))
+        .thumbnail(target.face())
+        .colour(colors::MOON)
+        .description(
+            [
+                pretty_message(
+                    icon::PLUS,
+                    format!("Nível {}", bold(progress.level.to_string())),
+                ),
+                pretty_message(
+                    icon::HASTAG,
+                    format!(
+                        "Posição no servidor: {}",
+                        bold(format!("#{}", entry.position))
+                    ),
+                ),
+                pretty_message(
+                    icon::TIMER,
+                    format!(
+                        "{} {}/{} XP",
+                        progress_bar(progress.current, progress.needed),
+                        format_currency(progress.current),
+                        format_currency(progress.needed)
+                    ),
+                ),
+            ]
+            .join("\n"),
+        )
+        .footer(CreateEmbedFooter::new(format!(
+            "XP total: {} • próximo nível rende {} moedas",
+            format_currency(entry.xp),
+            format_currency(levels::level_up_reward(progress.level, progress.level + 1))
+        )));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Veja quem mais conversa neste servidor.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "ranking",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler"
+)]
+pub async fn level_ranking(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let guild_id = guild_id.get() as i64;
+    let db = ctx.data().database.clone();
+    let entries = database::level::top(&db, guild_id, LEVEL_RANKING_FETCH_LIMIT).await?;
+
+    if entries.is_empty() {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(
+                    icon::EMPTY,
+                    "Ninguém ganhou XP neste servidor ainda.",
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let author = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let own_entry = database::level::find_position(&db, guild_id, author.id).await?;
+    let footer_rank = match own_entry {
+        Some(entry) => format!(
+            "Sua posição: {}º (nível {})",
+            entry.position,
+            levels::progress_for(entry.xp).level
+        ),
+        None => "Você ainda não aparece neste ranking".to_string(),
+    };
+
+    let pages = build_ranking_pages(&entries);
+    paginate(
+        ctx,
+        pages.len(),
+        LEVEL_RANKING_TIMEOUT,
+        false,
+        0,
+        move |current_page, total_pages| {
+            let embed = serenity::CreateEmbed::new()
+                .title(format!("{} Ranking de níveis", icon::PLUS))
+                .colour(colors::MOON)
+                .description(pages[current_page].clone())
+                .footer(CreateEmbedFooter::new(format!(
+                    "Página {}/{} • {}",
+                    current_page + 1,
+                    total_pages,
+                    footer_rank
+                )));
+            (embed, Vec::new())
+        },
+    )
+    .await
+}
+
+fn build_ranking_pages(entries: &[LevelEntryModel]) -> Vec<String> {
+    entries
+        .chunks(LEVEL_RANKING_PAGE_SIZE)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|entry| {
+                    format!(
+                        "{} {} — nível {} • {} XP",
+                        bold(format!("{}º", entry.position)),
+                        mention(entry.discord_id),
+                        levels::progress_for(entry.xp).level,
+                        format_currency(entry.xp)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect()
+}
+
+fn progress_bar(current: i64, needed: i64) -> String {
+    let filled = if needed > 0 {
+        ((current * PROGRESS_BAR_WIDTH as i64) / needed) as usize
+    } else {
+        0
+    }
+    .min(PROGRESS_BAR_WIDTH);
+    format!(
+        "{}{}",
+        "▰".repeat(filled),
+        "▱".repeat(PROGRESS_BAR_WIDTH - filled)
+    )
+}
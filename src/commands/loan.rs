@@ -0,0 +1,311 @@
+use crate::{
+    Context, Error,
+    commands::economy::RESET_CONFIG,
+    constants::{colors, icon},
+    database::{self, LoanModel, UserModel, loan::LoanStatus},
+    functions::{
+        format::{discord::bold, format_currency, pretty_message},
+        time::{self, ResetPeriod},
+    },
+};
+use chrono::{DateTime, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::builder::CreateEmbedFooter;
+
+/// Flat interest charged on every loan, in percent of the principal
+const INTEREST_PERCENT: i64 = 10;
+const LIMIT_PER_CLAIM: i64 = 200;
+const LIMIT_PER_DAY_OF_AGE: i64 = 50;
+const MAX_LOAN: i64 = 50_000;
+
+/// Peça moedas emprestadas e acompanhe sua dívida.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "empréstimo",
+    category = "Economia",
+    interaction_context = "Guild",
+    on_error = "crate::commands::util::command_error_handler",
+    subcommands("loan_request", "loan_pay", "loan_status")
+)]
+pub async fn loan(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Solicite um empréstimo de moedas.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "solicitar",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn loan_request(
+    ctx: Context<'_>,
+    #[description = "Quantidade de moedas a pedir"] valor: i64,
+) -> Result<(), Error> {
+    if valor <= 0 {
+        return reply(
+            &ctx,
+            pretty_message(icon::ERROR, "O valor precisa ser positivo."),
+        )
+        .await;
+    }
+
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let now = Utc::now();
+
+    if let Some(loan) = database::loan::find_open(&db, user.id, now).await? {
+        return reply(
+            &ctx,
+            pretty_message(
+                icon::ERROR,
+                format!(
+                    "Você já tem um empréstimo em aberto de {} moedas.",
+                    bold(format_currency(loan.outstanding()))
+                ),
+            ),
+        )
+        .await;
+    }
+
+    let limit = borrowing_limit(&db, &user, now).await?;
+    if valor > limit {
+        return reply(
+            &ctx,
+            pretty_message(
+                icon::ERROR,
+                format!(
+                    "Seu limite atual é de {} moedas.",
+                    bold(format_currency(limit))
+                ),
+            ),
+        )
+        .await;
+    }
+
+    let interest = valor * INTEREST_PERCENT / 100;
+    let due_at = time::next_reset_from(now, ResetPeriod::Weekly, &RESET_CONFIG);
+    let Some((user, loan)) = database::loan::take(&db, user.id, valor, interest, due_at).await?
+    else {
+        return reply(
+            &ctx,
+            pretty_message(icon::ERROR, "Você já tem um empréstimo em aberto."),
+        )
+        .await;
+    };
+
+    let content = [
+        pretty_message(
+            icon::CHECK,
+            format!("Você recebeu {} moedas.", bold(format_currency(valor))),
+        ),
+        pretty_message(
+            icon::DOLLAR,
+            format!(
+                "Total a pagar: {} moedas ({}% de juros)",
+                bold(format_currency(loan.amount_due)),
+                INTEREST_PERCENT
+            ),
+        ),
+        pretty_message(
+            icon::ALARM,
+            format!("Vencimento: {}", time::describe_absolute(due_at)),
+        ),
+        pretty_message(
+            icon::PLUS,
+            format!(
+                "Carteira: {} moedas",
+                bold(format_currency(user.dollars.max(0)))
+            ),
+        ),
+    ]
+    .join("\n");
+    reply(&ctx, content).await
+}
+
+/// Pague seu empréstimo com moedas da carteira.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "pagar",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn loan_pay(
+    ctx: Context<'_>,
+    #[description = "Quantidade a pagar (padrão: tudo o que for possível)"] valor: Option<i64>,
+) -> Result<(), Error> {
+    if valor.is_some_and(|amount| amount <= 0) {
+        return reply(
+            &ctx,
+            pretty_message(icon::ERROR, "O valor precisa ser positivo."),
+        )
+        .await;
+    }
+
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+
+    if database::loan::find_open(&db, user.id, Utc::now())
+        .await?
+        .is_none()
+    {
+        return reply(
+            &ctx,
+            pretty_message(icon::ERROR, "Você não tem empréstimos em aberto."),
+        )
+        .await;
+    }
+
+    let max_amount = valor.unwrap_or(i64::MAX);
+    let Some(repayment) = database::loan::repay(&db, user.id, max_amount, "loan_repayment").await?
+    else {
+        return reply(
+            &ctx,
+            pretty_message(
+                icon::ERROR,
+                "Você não possui moedas para pagar o empréstimo.",
+            ),
+        )
+        .await;
+    };
+
+    let status_line = if repayment.loan.status() == Some(LoanStatus::Repaid) {
+        pretty_message(icon::CHECK, "Empréstimo quitado!")
+    } else {
+        pretty_message(
+            icon::DOLLAR,
+            format!(
+                "Restante: {} moedas",
+                bold(format_currency(repayment.loan.outstanding()))
+            ),
+        )
+    };
+    let content = [
+        pretty_message(
+            icon::MINUS,
+            format!(
+                "Você pagou {} moedas.",
+                bold(format_currency(repayment.amount))
+            ),
+        ),
+        status_line,
+        pretty_message(
+            icon::PLUS,
+            format!(
+                "Carteira: {} moedas",
+                bold(format_currency(repayment.user.dollars.max(0)))
+            ),
+        ),
+    ]
+    .join("\n");
+    reply(&ctx, content).await
+}
+
+/// Consulte seu empréstimo e seu limite.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "status",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn loan_status(ctx: Context<'_>) -> Result<(), Error> {
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let now = Utc::now();
+    let loan = database::loan::find_open(&db, user.id, now).await?;
+    let limit = borrowing_limit(&db, &user, now).await?;
+
+    let description = match loan.as_ref() {
+        Some(loan) => describe_loan(loan),
+        None => pretty_message(icon::CHECK, "Nenhum empréstimo em aberto."),
+    };
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!(
+            "{} Empréstimos de {}",
+            icon::DOLLAR,
+            ctx.author().name
+        ))
+        .colour(colors::MOON)
+        .description(description)
+        .field(
+            "Limite disponível",
+            format!("{} moedas", bold(format_currency(limit))),
+            true,
+        )
+        .field("Juros", format!("{INTEREST_PERCENT}%"), true)
+        .footer(CreateEmbedFooter::new(
+            "Metade das moedas de cada recompensa coletada abate a dívida automaticamente",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Limit grows with claimed rewards and account age
+async fn borrowing_limit(
+    db: &sqlx::SqlitePool,
+    user: &UserModel,
+    now: DateTime<Utc>,
+) -> Result<i64, Error> {
+    let claims = database::loan::total_reward_claims(db, user.id).await?;
+    let age_days = DateTime::parse_from_rfc3339(&user.created_at)
+        .map(|created_at| (now - created_at.with_timezone(&Utc)).num_days().max(0))
+        .unwrap_or(0);
+
+    Ok((claims * LIMIT_PER_CLAIM + age_days * LIMIT_PER_DAY_OF_AGE).min(MAX_LOAN))
+}
+
+fn describe_loan(loan: &LoanModel) -> String {
+    let mut lines = vec![
+        pretty_message(
+            icon::DOLLAR,
+            format!(
+                "Devendo: {} de {} moedas",
+                bold(format_currency(loan.outstanding())),
+                format_currency(loan.amount_due)
+            ),
+        ),
+        pretty_message(
+            icon::PLUS,
+            format!(
+                "Principal: {} • Juros: {}",
+                format_currency(loan.principal),
+                format_currency(loan.interest)
+            ),
+        ),
+    ];
+
+    if let Some(due_at) = loan.due_datetime() {
+        lines.push(pretty_message(
+            icon::ALARM,
+            format!("Vencimento: {}", time::describe_absolute(due_at)),
+        ));
+    }
+    if loan.status() == Some(LoanStatus::Defaulted) {
+        lines.push(pretty_message(
+            icon::ERROR,
+            "Empréstimo vencido: apostas no Mines estão bloqueadas até a quitação.",
+        ));
+    }
+
+    lines.join("\n")
+}
+
+async fn reply(ctx: &Context<'_>, content: String) -> Result<(), Error> {
+    ctx.send(
+        poise::CreateReply::default()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
@@ -61,6 +61,36 @@ impl Mode {
         }
     }
 
+    pub fn register_mismatch(&mut self, user_id: serenity::UserId) {
+        match self {
+            Mode::Solo { player } => {
+                if player.user.id == user_id {
+                    player.mismatches += 1;
+                }
+            }
+            Mode::Versus { players, .. } => {
+                if let Some(player) = players.iter_mut().find(|p| p.user.id == user_id) {
+                    player.mismatches += 1;
+                }
+            }
+        }
+    }
+
+    /// The solo player once the board is cleared, or the versus player with more pairs
+    pub fn winner(&self) -> Option<&PlayerState> {
+        match self {
+            Mode::Solo { player } => Some(player),
+            Mode::Versus { players, .. } => {
+                let [left, right] = players;
+                match left.score.cmp(&right.score) {
+                    std::cmp::Ordering::Greater => Some(left),
+                    std::cmp::Ordering::Less => Some(right),
+                    std::cmp::Ordering::Equal => None,
+                }
+            }
+        }
+    }
+
     pub fn advance_turn(&mut self) {
         if let Mode::Versus { current_turn, .. } = self {
             *current_turn = (*current_turn + 1) % 2;
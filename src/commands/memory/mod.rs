@@ -2,6 +2,7 @@ use crate::{
     Context, Error,
     constants::{colors, icon},
     functions::{
+        achievements::{self, AchievementEvent},
         format::{discord::bold, pretty_message},
         interactions::{
             component::{send_ephemeral_response, update_component_message},
@@ -237,7 +238,22 @@ async fn run_game(
                 update_component_message(&ctx, &interaction, embed, components).await?;
 
                 if finished {
-                    state.set_status(state.mode.finish_message(state.attempts));
+                    let mut status = state.mode.finish_message(state.attempts);
+                    if let Some(winner) = state.mode.winner() {
+                        let unlocked = achievements::record(
+                            &ctx.data().database,
+                            winner.user.id.get() as i64,
+                            AchievementEvent::MemoryWon {
+                                mismatches: winner.mismatches,
+                                versus: !state.mode.is_strict_single_player(),
+                            },
+                        )
+                        .await?;
+                        if let Some(lines) = achievements::format_unlocks(&unlocked) {
+                            status = format!("{status}\n{lines}");
+                        }
+                    }
+                    state.set_status(status);
                     let (embed, components) = render_game(&state, None);
                     channel_id
                         .edit_message(
@@ -254,6 +270,7 @@ async fn run_game(
             }
             SelectionResult::Mismatch { pair } => {
                 state.locked = true;
+                state.mode.register_mismatch(interaction.user.id);
                 state.set_status(pretty_message(
                     icon::ERROR,
                     format!("{} não acertou o par.", interaction.user.mention()),
@@ -4,10 +4,15 @@ use poise::serenity_prelude as serenity;
 pub struct PlayerState {
     pub user: serenity::User,
     pub score: u32,
+    pub mismatches: u32,
 }
 
 impl PlayerState {
     pub fn new(user: serenity::User) -> Self {
-        Self { user, score: 0 }
+        Self {
+            user,
+            score: 0,
+            mismatches: 0,
+        }
     }
 }
@@ -14,6 +14,7 @@ pub struct MinesGameState {
     pub gave_up: bool,
     pub cashed_out_amount: Option<i64>,
     pub refunded: bool,
+    pub achievements_text: Option<String>,
 }
 
 impl MinesGameState {
@@ -30,6 +31,7 @@ impl MinesGameState {
             gave_up: false,
             cashed_out_amount: None,
             refunded: false,
+            achievements_text: None,
         }
     }
 
@@ -47,6 +49,22 @@ impl MinesGameState {
         }
     }
 
+    /// One character per tile, `1` for bombs, as stored in `game_sessions.layout`
+    pub fn layout_code(&self) -> String {
+        self.tiles
+            .iter()
+            .map(|tile| if tile.is_bomb { '1' } else { '0' })
+            .collect()
+    }
+
+    /// One character per tile, `1` for opened ones, as stored in `game_sessions.revealed`
+    pub fn revealed_code(&self) -> String {
+        self.tiles
+            .iter()
+            .map(|tile| if tile.revealed { '1' } else { '0' })
+            .collect()
+    }
+
     pub fn reveal_all(&mut self) {
         for tile in &mut self.tiles {
             tile.revealed = true;
@@ -1,8 +1,10 @@
 use crate::{
     Context, Error,
     constants::{colors, icon},
-    database::{self, UserModel},
+    database::{self, Currency, UserModel, WalletChange},
     functions::{
+        achievements::{self, AchievementEvent},
+        bot::gambling,
         format::{discord::bold, format_currency, pretty_message},
         interactions::component::{send_ephemeral_response, update_component_message},
     },
@@ -29,6 +31,8 @@ const HIDDEN_LABEL: &str = "❔";
 const MIN_WAGER: i64 = 50;
 const MAX_WAGER: i64 = 50_000;
 const GAME_TIMEOUT: Duration = Duration::from_secs(180);
+/// Identifies mines rows in `game_sessions`
+pub const GAME_NAME: &str = "mines";
 
 /// Teste sua sorte contra as bombas!
 #[poise::command(
@@ -76,14 +80,26 @@ pub async fn mines(
         return Ok(());
     }
 
+    let Some(_session) = gambling::claim_wager_session(&ctx).await? else {
+        return Ok(());
+    };
+
     let player = ctx.author().clone();
     let discord_id = player.id.get() as i64;
     let db = ctx.data().database.clone();
 
-    let mut user = database::get_or_create_user(&db, discord_id).await?;
-    let mut wager_transaction_id: Option<i32>;
+    let user = database::get_or_create_user(&db, discord_id).await?;
+    if !gambling::ensure_can_wager(&ctx, user.id, valor).await? {
+        return Ok(());
+    }
+
+    let mut state = MinesGameState::new(valor);
+    let wager = WalletChange::new(Currency::Dollars, -valor, "mines_wager")
+        .with_context("Entrada no Mines");
 
-    if user.dollars < valor {
+    let Some((wager, session_id)) =
+        database::game_session::start(&db, GAME_NAME, user.id, wager, &state.layout_code()).await?
+    else {
         ctx.send(
             poise::CreateReply::default()
                 .content(pretty_message(
@@ -94,25 +110,9 @@ pub async fn mines(
         )
         .await?;
         return Ok(());
-    }
-
-    wager_transaction_id = {
-        user.dollars -= valor;
-        user = database::update_user_balance(&db, user.id, user.dollars, user.diamonds).await?;
-        let transaction = database::insert_currency_transaction(
-            &db,
-            user.id,
-            -valor,
-            user.dollars,
-            "dollars",
-            "mines_wager",
-            Some("Entrada no Mines".to_string()),
-        )
-        .await?;
-        Some(transaction.id)
     };
 
-    let mut state = MinesGameState::new(valor);
+    let mut user = wager.user;
     state.set_status(pretty_message(
         icon::BELL,
         format!(
@@ -135,6 +135,13 @@ pub async fn mines(
     let message = reply.message().await?;
     let message_id = message.id;
     let channel_id = message.channel_id;
+    database::game_session::attach_message(
+        &db,
+        session_id,
+        channel_id.get() as i64,
+        message_id.get() as i64,
+    )
+    .await?;
     let cashout_id = format!("{}cashout", state.custom_id_prefix);
     let giveup_id = format!("{}giveup", state.custom_id_prefix);
 
@@ -166,6 +173,8 @@ pub async fn mines(
                     .await?;
                 }
                 Some(RevealOutcome::Diamond) => {
+                    database::game_session::record_reveal(&db, session_id, &state.revealed_code())
+                        .await?;
                     if state.can_cash_out() {
                         state.set_status(pretty_message(
                             icon::CHECK,
@@ -188,7 +197,7 @@ pub async fn mines(
 
                     if state.force_cashout_reached() {
                         let payout = state.projected_payout();
-                        finalize_cashout(&ctx, &mut state, &mut user, payout, &player, true)
+                        finalize_cashout(&ctx, &mut state, session_id, payout, &player, true)
                             .await?;
                         let (embed, components) = render_game(&state, &player);
                         update_component_message(&ctx, &interaction, embed, components).await?;
@@ -199,6 +208,7 @@ pub async fn mines(
                     }
                 }
                 Some(RevealOutcome::Bomb) => {
+                    database::game_session::close(&db, session_id, "busted", None).await?;
                     state.busted = true;
                     state.reveal_all();
                     state.set_status(pretty_message(
@@ -234,7 +244,7 @@ pub async fn mines(
             }
 
             let payout = state.projected_payout();
-            finalize_cashout(&ctx, &mut state, &mut user, payout, &player, false).await?;
+            finalize_cashout(&ctx, &mut state, session_id, payout, &player, false).await?;
             let (embed, components) = render_game(&state, &player);
             update_component_message(&ctx, &interaction, embed, components).await?;
             break;
@@ -251,8 +261,7 @@ pub async fn mines(
 
             state.gave_up = true;
             state.reveal_all();
-            let transaction_id = wager_transaction_id.take();
-            refund_wager(&ctx, &mut user, state.wager, transaction_id).await?;
+            refund_wager(&ctx, &mut user, session_id).await?;
             state.refunded = true;
             state.set_status(pretty_message(
                 icon::CHECK,
@@ -271,6 +280,7 @@ pub async fn mines(
     }
 
     if !state.is_finished() {
+        database::game_session::close(&db, session_id, "expired", None).await?;
         state.gave_up = true;
         state.reveal_all();
         state.set_status(pretty_message(
@@ -293,7 +303,7 @@ pub async fn mines(
 async fn finalize_cashout(
     ctx: &Context<'_>,
     state: &mut MinesGameState,
-    user: &mut UserModel,
+    session_id: i32,
     payout: i64,
     player: &serenity::User,
     forced: bool,
@@ -303,8 +313,6 @@ async fn finalize_cashout(
     }
 
     let db = ctx.data().database.clone();
-    user.dollars += payout;
-    *user = database::update_user_balance(&db, user.id, user.dollars, user.diamonds).await?;
     let context = if forced {
         "Resgate automático"
     } else {
@@ -315,19 +323,23 @@ async fn finalize_cashout(
     } else {
         "mines_cashout"
     };
-    database::insert_currency_transaction(
+    let change = WalletChange::new(Currency::Dollars, payout, kind).with_context(context);
+    database::game_session::close(&db, session_id, "cashed_out", Some(change)).await?;
+
+    state.cashed_out_amount = Some(payout);
+    state.reveal_all();
+
+    // The auto-cashout is the furthest a round can go, so it carries the highest multiplier
+    let unlocked = achievements::record(
         &db,
-        user.id,
-        payout,
-        user.dollars,
-        "dollars",
-        kind,
-        Some(context.to_string()),
+        player.id.get() as i64,
+        AchievementEvent::MinesCashout {
+            max_multiplier: forced,
+        },
     )
     .await?;
+    state.achievements_text = achievements::format_unlocks(&unlocked);
 
-    state.cashed_out_amount = Some(payout);
-    state.reveal_all();
     let message = if forced {
         pretty_message(
             icon::GIFT,
@@ -354,14 +366,19 @@ async fn finalize_cashout(
 async fn refund_wager(
     ctx: &Context<'_>,
     user: &mut UserModel,
-    amount: i64,
-    transaction_id: Option<i32>,
+    session_id: i32,
 ) -> Result<(), Error> {
     let db = ctx.data().database.clone();
-    user.dollars += amount;
-    *user = database::update_user_balance(&db, user.id, user.dollars, user.diamonds).await?;
-    if let Some(id) = transaction_id {
-        database::delete_currency_transaction(&db, id).await?;
+    if let Some(update) = database::game_session::close_with_refund(
+        &db,
+        session_id,
+        "cancelled",
+        "mines_refund",
+        "Reembolso do Mines",
+    )
+    .await?
+    {
+        *user = update.user;
     }
     Ok(())
 }
@@ -440,6 +457,14 @@ fn render_game(
         );
     }
 
+    if let Some(unlocked) = &state.achievements_text {
+        embed = embed.field(
+            format!("{} Conquistas", icon::GIFT),
+            unlocked.clone(),
+            false,
+        );
+    }
+
     if let Some(status) = &state.status_text {
         embed = embed.description(status.clone());
     }
@@ -1,13 +1,20 @@
 use crate::{Data, Error};
 
+pub mod achievements;
+pub mod bank;
 pub mod blacklist;
 pub mod economy;
+pub mod gambling;
 pub mod help;
 pub mod jokenpo;
+pub mod level;
+pub mod loan;
 pub mod memory;
 pub mod mines;
 pub mod ping;
+pub mod profile;
 pub mod race;
+pub mod shop;
 pub mod util;
 
 pub fn load_all() -> Vec<poise::Command<Data, Error>> {
@@ -16,9 +23,19 @@ pub fn load_all() -> Vec<poise::Command<Data, Error>> {
         ping::ping(),
         jokenpo::jokenpo(),
         economy::economy(),
+        bank::bank(),
+        loan::loan(),
+        gambling::gambling(),
         memory::memory(),
         mines::mines(),
         race::race(),
+        shop::shop(),
+        shop::inventory(),
+        profile::profile(),
+        profile::profile_context(),
+        profile::privacy(),
+        achievements::achievements(),
+        level::level(),
         blacklist::blacklist(),
     ]
 }
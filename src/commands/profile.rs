@@ -0,0 +1,209 @@
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{self, LeaderboardMetric, RewardStateModel},
+    functions::{
+        format::{discord::bold, format_currency, pretty_message},
+        time,
+    },
+};
+use chrono::{DateTime, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::builder::{CreateEmbedAuthor, CreateEmbedFooter};
+
+const REWARD_LABELS: [(&str, &str); 3] = [
+    ("daily", "Diárias"),
+    ("weekly", "Semanais"),
+    ("monthly", "Mensais"),
+];
+
+/// Veja o perfil econômico de alguém.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "perfil",
+    category = "Economia",
+    interaction_context = "Guild",
+    on_error = "crate::commands::util::command_error_handler"
+)]
+pub async fn profile(
+    ctx: Context<'_>,
+    #[description = "Ver o perfil de outra pessoa"] usuario: Option<serenity::User>,
+) -> Result<(), Error> {
+    let target = usuario.unwrap_or_else(|| ctx.author().clone());
+    send_profile(ctx, &target).await
+}
+
+/// Veja o perfil econômico deste usuário.
+#[poise::command(
+    context_menu_command = "Ver perfil",
+    hide_in_help,
+    category = "Economia",
+    interaction_context = "Guild",
+    on_error = "crate::commands::util::command_error_handler"
+)]
+pub async fn profile_context(ctx: Context<'_>, usuario: serenity::User) -> Result<(), Error> {
+    send_profile(ctx, &usuario).await
+}
+
+/// Escolha o que outras pessoas podem ver no seu perfil.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "privacidade",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn privacy(
+    ctx: Context<'_>,
+    #[description = "Esconder seu saldo de outras pessoas"] ocultar_saldo: bool,
+) -> Result<(), Error> {
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    database::settings::set_hide_balance(&db, user.id, ocultar_saldo).await?;
+
+    let message = if ocultar_saldo {
+        "Seu saldo agora fica oculto para outras pessoas no /perfil e nos rankings de saldo."
+    } else {
+        "Seu saldo agora fica visível para todos no /perfil e nos rankings de saldo."
+    };
+    ctx.send(
+        poise::CreateReply::default()
+            .content(pretty_message(icon::CHECK, message))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+async fn send_profile(ctx: Context<'_>, target: &serenity::User) -> Result<(), Error> {
+    if target.bot {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(icon::ERROR, "Bots não têm perfil."))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, target.id.get() as i64).await?;
+    let settings = database::settings::get(&db, user.id).await?;
+    let reward_states = database::get_all_reward_states(&db, user.id).await?;
+    let games = database::profile::game_stats(&db, user.id).await?;
+    let show_balance = !settings.hide_balance || target.id == ctx.author().id;
+
+    let mut embed = serenity::CreateEmbed::new()
+        .author(CreateEmbedAuthor::new(format!("Perfil de {}", target.name)))
+        .thumbnail(target.face())
+        .colour(colors::MOON);
+
+    if show_balance {
+        let position =
+            database::leaderboard::find_position(&db, LeaderboardMetric::Dollars, None, user.id)
+                .await?;
+        let position_line = match position {
+            Some(entry) => format!(
+                "Posição no ranking: {}",
+                bold(format!("#{}", entry.position))
+            ),
+            None if settings.hide_balance => "Fora do ranking (saldo oculto)".to_string(),
+            None => "Fora do ranking".to_string(),
+        };
+        embed = embed.field(
+            "Saldo",
+            [
+                pretty_message(
+                    icon::DOLLAR,
+                    format!("{} moedas", bold(format_currency(user.dollars))),
+                ),
+                pretty_message(
+                    icon::DIAMOND,
+                    format!("{} diamantes", bold(format_currency(user.diamonds))),
+                ),
+                pretty_message(icon::HASTAG, position_line),
+            ]
+            .join("\n"),
+            true,
+        );
+    } else {
+        embed = embed.field(
+            "Saldo",
+            pretty_message(icon::EMPTY, "Oculto por privacidade"),
+            true,
+        );
+    }
+
+    let created_at = DateTime::parse_from_rfc3339(&user.created_at)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok();
+    let account_line = match created_at {
+        Some(created_at) => format!(
+            "Conta criada {} ({} dias)",
+            time::describe_relative(created_at),
+            (Utc::now() - created_at).num_days().max(0)
+        ),
+        None => "Data de criação desconhecida".to_string(),
+    };
+
+    embed = embed
+        .description(pretty_message(icon::ALARM, account_line))
+        .field("Recompensas", format_rewards(&reward_states), true)
+        .field(
+            "Mines",
+            [
+                pretty_message(
+                    icon::HASTAG,
+                    format!("Partidas: {}", bold(format_currency(games.mines_played))),
+                ),
+                pretty_message(
+                    icon::GIFT,
+                    format!(
+                        "Maior resgate: {} moedas",
+                        bold(format_currency(games.biggest_cashout))
+                    ),
+                ),
+                pretty_message(
+                    if games.mines_net < 0 {
+                        icon::MINUS
+                    } else {
+                        icon::PLUS
+                    },
+                    format!(
+                        "Resultado: {} moedas",
+                        bold(format_currency(games.mines_net))
+                    ),
+                ),
+            ]
+            .join("\n"),
+            false,
+        )
+        .footer(CreateEmbedFooter::new(
+            "Use /privacidade para esconder seu saldo de outras pessoas",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+fn format_rewards(states: &[RewardStateModel]) -> String {
+    let total: i64 = states.iter().map(|state| state.total_claims).sum();
+    let mut lines: Vec<String> = REWARD_LABELS
+        .iter()
+        .map(|(reward_type, label)| {
+            let claims = states
+                .iter()
+                .find(|state| state.reward_type == *reward_type)
+                .map(|state| state.total_claims)
+                .unwrap_or(0);
+            pretty_message(icon::GIFT, format!("{label}: {}", bold(claims.to_string())))
+        })
+        .collect();
+    lines.push(pretty_message(
+        icon::PLUS,
+        format!("Total: {}", bold(total.to_string())),
+    ));
+    lines.join("\n")
+}
@@ -1,19 +1,19 @@
 use crate::{
     Context, Error,
     constants::{colors, icon},
-    functions::format::pretty_message,
+    functions::{
+        achievements::{self, AchievementEvent},
+        format::pretty_message,
+        session::SessionRegistry,
+    },
 };
 use lobby::{LobbyMessageHandle, LobbyOutcome, RaceLobby};
 use poise::serenity_prelude::{self as serenity, Mentionable};
 use progress_message::RaceProgressMessage;
 use serenity::builder::EditMessage;
 use state::{RaceContestant, RaceResultEntry, RaceState};
-use std::{
-    collections::HashSet,
-    sync::{Arc, OnceLock},
-    time::Duration,
-};
-use tokio::{sync::Mutex, time::sleep};
+use std::{sync::OnceLock, time::Duration};
+use tokio::time::sleep;
 
 mod lobby;
 mod progress_message;
@@ -27,7 +27,10 @@ const MIN_STEP_PER_ROUND: usize = 1;
 const MAX_STEP_PER_ROUND: usize = 3;
 const MAX_ANIMALS_PER_RACE: usize = 8;
 
-static ACTIVE_CHANNELS: OnceLock<Arc<Mutex<HashSet<serenity::ChannelId>>>> = OnceLock::new();
+/// A channel stays claimed for at most this long, even if a race never finishes
+const CHANNEL_MAX_HOLD: Duration = Duration::from_secs(15 * 60);
+
+static ACTIVE_CHANNELS: OnceLock<SessionRegistry<serenity::ChannelId>> = OnceLock::new();
 
 /// Crie uma corrida de animais.
 #[poise::command(
@@ -40,7 +43,10 @@ static ACTIVE_CHANNELS: OnceLock<Arc<Mutex<HashSet<serenity::ChannelId>>>> = Onc
     on_error = "crate::commands::util::command_error_handler"
 )]
 pub async fn race(ctx: Context<'_>) -> Result<(), Error> {
-    let Some(_) = claim_channel(ctx.channel_id()).await else {
+    let Some(_channel_guard) = ACTIVE_CHANNELS
+        .get_or_init(|| SessionRegistry::new(CHANNEL_MAX_HOLD))
+        .claim(ctx.channel_id())
+    else {
         ctx.send(
             poise::CreateReply::default()
                 .content(pretty_message(
@@ -108,7 +114,20 @@ async fn run_race(
     let winners = state.winners();
     let rankings = state.rankings();
 
-    announce_results(&ctx, &lobby_message, &winners, &rankings).await?;
+    let mut unlocked_lines = Vec::new();
+    for winner in &winners {
+        let unlocked = achievements::record(
+            &ctx.data().database,
+            winner.user.id.get() as i64,
+            AchievementEvent::RaceWon,
+        )
+        .await?;
+        if let Some(lines) = achievements::format_unlocks(&unlocked) {
+            unlocked_lines.push(format!("{}\n{lines}", winner.user.mention()));
+        }
+    }
+
+    announce_results(&ctx, &lobby_message, &winners, &rankings, &unlocked_lines).await?;
 
     Ok(())
 }
@@ -129,8 +148,12 @@ async fn announce_results(
     handle: &LobbyMessageHandle,
     winners: &[RaceContestant],
     rankings: &[RaceResultEntry],
+    unlocked_lines: &[String],
 ) -> Result<(), Error> {
-    let embed = build_results_embed(winners, rankings);
+    let mut embed = build_results_embed(winners, rankings);
+    if !unlocked_lines.is_empty() {
+        embed = embed.field("Conquistas", unlocked_lines.join("\n"), false);
+    }
     handle
         .channel_id
         .edit_message(
@@ -188,34 +211,3 @@ fn build_results_embed(
 
     embed
 }
-
-async fn claim_channel(channel_id: serenity::ChannelId) -> Option<RaceChannelGuard> {
-    let active = ACTIVE_CHANNELS
-        .get_or_init(|| Arc::new(Mutex::new(HashSet::new())))
-        .clone();
-
-    let mut lock = active.lock().await;
-    if lock.contains(&channel_id) {
-        return None;
-    }
-    lock.insert(channel_id);
-    drop(lock);
-
-    Some(RaceChannelGuard { channel_id, active })
-}
-
-struct RaceChannelGuard {
-    channel_id: serenity::ChannelId,
-    active: Arc<Mutex<HashSet<serenity::ChannelId>>>,
-}
-
-impl Drop for RaceChannelGuard {
-    fn drop(&mut self) {
-        let channel_id = self.channel_id;
-        let active = Arc::clone(&self.active);
-        tokio::spawn(async move {
-            let mut lock = active.lock().await;
-            lock.remove(&channel_id);
-        });
-    }
-}
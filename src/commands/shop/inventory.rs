@@ -0,0 +1,100 @@
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{self, InventoryEntryModel, shop::ItemKind},
+    functions::{
+        format::{
+            discord::{bold, italic},
+            pretty_message,
+        },
+        interactions::pagination::paginate,
+        time,
+    },
+};
+use poise::serenity_prelude as serenity;
+use serenity::builder::CreateEmbedFooter;
+use std::time::Duration;
+
+const INVENTORY_PAGE_SIZE: usize = 8;
+const INVENTORY_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Veja os itens que você possui.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "inventario",
+    aliases("inventário", "inv"),
+    category = "Economia",
+    interaction_context = "Guild",
+    on_error = "crate::commands::util::command_error_handler"
+)]
+pub async fn inventory(
+    ctx: Context<'_>,
+    #[description = "Ver o inventário de outra pessoa"] usuario: Option<serenity::User>,
+) -> Result<(), Error> {
+    let target = usuario.as_ref().unwrap_or_else(|| ctx.author());
+    let db = ctx.data().database.clone();
+    let user = database::get_or_create_user(&db, target.id.get() as i64).await?;
+    let entries = database::inventory::list_by_user(&db, user.id).await?;
+
+    if entries.is_empty() {
+        let message = if target.id == ctx.author().id {
+            "Seu inventário está vazio. Confira a /loja!".to_string()
+        } else {
+            format!("{} ainda não possui itens.", target.name)
+        };
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(icon::EMPTY, message))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let pages: Vec<String> = entries
+        .chunks(INVENTORY_PAGE_SIZE)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(format_entry)
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        })
+        .collect();
+    let owner_name = target.name.clone();
+    let total_items = entries.len();
+
+    paginate(
+        ctx,
+        pages.len(),
+        INVENTORY_TIMEOUT,
+        false,
+        0,
+        move |current_page, total_pages| {
+            let embed = serenity::CreateEmbed::new()
+                .title(format!("{} Inventário de {}", icon::GIFT, owner_name))
+                .colour(colors::MOON)
+                .description(pages[current_page].clone())
+                .footer(CreateEmbedFooter::new(format!(
+                    "Página {}/{} • {} itens diferentes",
+                    current_page + 1,
+                    total_pages,
+                    total_items
+                )));
+            (embed, Vec::new())
+        },
+    )
+    .await
+}
+
+fn format_entry(entry: &InventoryEntryModel) -> String {
+    let header = match ItemKind::from_db_name(&entry.kind) {
+        Some(ItemKind::Cosmetic) => format!("{} • Cosmético", bold(&entry.name)),
+        _ => format!("{} • {}x", bold(&entry.name), entry.quantity),
+    };
+    let acquired = time::describe_relative_from_str(&entry.acquired_at)
+        .map(|when| format!("Adquirido {when}"))
+        .unwrap_or_default();
+    format!("{header}\n{}\n{acquired}", italic(&entry.description))
+}
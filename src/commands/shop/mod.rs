@@ -0,0 +1,238 @@
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{
+        self, Currency, ShopItemModel,
+        shop::{ItemKind, PurchaseOutcome},
+    },
+    functions::{
+        format::{
+            discord::{bold, inline_code, italic},
+            format_currency, pretty_message,
+        },
+        interactions::pagination::paginate,
+    },
+};
+use poise::serenity_prelude as serenity;
+use serenity::builder::{CreateAutocompleteResponse, CreateEmbedFooter};
+use std::time::Duration;
+
+mod inventory;
+pub use inventory::inventory;
+
+const SHOP_PAGE_SIZE: usize = 5;
+const SHOP_TIMEOUT: Duration = Duration::from_secs(180);
+const MAX_PURCHASE_QUANTITY: i64 = 99;
+
+/// Gaste suas moedas e diamantes na loja.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "loja",
+    category = "Economia",
+    interaction_context = "Guild",
+    on_error = "crate::commands::util::command_error_handler",
+    subcommands("shop_browse", "shop_buy")
+)]
+pub async fn shop(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Veja os itens à venda.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "ver",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn shop_browse(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let items = database::shop::list_active(&ctx.data().database).await?;
+    if items.is_empty() {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(icon::EMPTY, "A loja está vazia no momento.")),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let pages: Vec<String> = items
+        .chunks(SHOP_PAGE_SIZE)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(format_item)
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        })
+        .collect();
+
+    paginate(
+        ctx,
+        pages.len(),
+        SHOP_TIMEOUT,
+        true,
+        0,
+        move |current_page, total_pages| {
+            let embed = serenity::CreateEmbed::new()
+                .title(format!("{} Loja", icon::GIFT))
+                .colour(colors::MOON)
+                .description(pages[current_page].clone())
+                .footer(CreateEmbedFooter::new(format!(
+                    "Página {}/{} • use /loja comprar para levar um item",
+                    current_page + 1,
+                    total_pages
+                )));
+            (embed, Vec::new())
+        },
+    )
+    .await
+}
+
+/// Compre um item da loja.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    rename = "comprar",
+    category = "Economia",
+    on_error = "crate::commands::util::command_error_handler",
+    ephemeral = true
+)]
+pub async fn shop_buy(
+    ctx: Context<'_>,
+    #[description = "Item que você quer comprar"]
+    #[autocomplete = "autocomplete_item"]
+    item: String,
+    #[description = "Quantidade (apenas consumíveis, padrão: 1)"]
+    #[min = 1]
+    #[max = 99]
+    quantidade: Option<i64>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let db = ctx.data().database.clone();
+    let Some(item) = database::shop::find_active_by_slug(&db, item.trim()).await? else {
+        ctx.send(poise::CreateReply::default().content(pretty_message(
+            icon::ERROR,
+            "Item não encontrado. Escolha uma das opções sugeridas.",
+        )))
+        .await?;
+        return Ok(());
+    };
+
+    let quantity = quantidade.unwrap_or(1);
+    let stackable = item.item_kind().is_some_and(ItemKind::is_stackable);
+    if !(1..=MAX_PURCHASE_QUANTITY).contains(&quantity) || (!stackable && quantity != 1) {
+        let message = if stackable {
+            "Escolha uma quantidade entre 1 e 99."
+        } else {
+            "Cosméticos só podem ser comprados uma vez."
+        };
+        ctx.send(poise::CreateReply::default().content(pretty_message(icon::ERROR, message)))
+            .await?;
+        return Ok(());
+    }
+
+    let user = database::get_or_create_user(&db, ctx.author().id.get() as i64).await?;
+    let total_price = item.price * quantity;
+    let price_text = format_price(total_price, &item.currency);
+
+    let content = match database::shop::purchase(&db, user.id, &item, quantity).await? {
+        PurchaseOutcome::Completed { user, owned } => {
+            let currency = item.price_currency().unwrap_or(Currency::Dollars);
+            format!(
+                "{}\n{}",
+                pretty_message(
+                    icon::CHECK,
+                    format!(
+                        "Você comprou {}x {} por {}.",
+                        quantity,
+                        bold(&item.name),
+                        price_text
+                    ),
+                ),
+                pretty_message(
+                    icon::DIAMOND,
+                    format!(
+                        "Agora você tem {} • Saldo: {}",
+                        bold(format!("{owned}x")),
+                        format_price(currency.balance_of(&user), &item.currency)
+                    ),
+                )
+            )
+        }
+        PurchaseOutcome::InsufficientFunds => pretty_message(
+            icon::ERROR,
+            format!("Saldo insuficiente. Você precisa de {price_text}."),
+        ),
+        PurchaseOutcome::AlreadyOwned => {
+            pretty_message(icon::MINUS, format!("Você já possui {}.", bold(&item.name)))
+        }
+    };
+
+    ctx.send(poise::CreateReply::default().content(content))
+        .await?;
+    Ok(())
+}
+
+async fn autocomplete_item(ctx: Context<'_>, partial: &str) -> CreateAutocompleteResponse {
+    let items = database::shop::list_active(&ctx.data().database)
+        .await
+        .unwrap_or_default();
+    let needle = partial.trim().to_lowercase();
+
+    items
+        .into_iter()
+        .filter(|item| {
+            needle.is_empty()
+                || item.slug.contains(&needle)
+                || item.name.to_lowercase().contains(&needle)
+        })
+        .take(25)
+        .fold(CreateAutocompleteResponse::new(), |acc, item| {
+            let label = format!(
+                "{} — {}",
+                item.name,
+                format_plain_price(item.price, &item.currency)
+            );
+            acc.add_string_choice(label, item.slug)
+        })
+}
+
+fn format_item(item: &ShopItemModel) -> String {
+    let kind_label = match item.item_kind() {
+        Some(ItemKind::Consumable) => "Consumível",
+        Some(ItemKind::Cosmetic) => "Cosmético (único)",
+        None => "Item",
+    };
+    format!(
+        "{}\n{}\n{} • {} • {}",
+        bold(&item.name),
+        italic(&item.description),
+        format_price(item.price, &item.currency),
+        kind_label,
+        inline_code(&item.slug)
+    )
+}
+
+fn format_price(amount: i64, currency: &str) -> String {
+    let icon = if currency == "diamonds" {
+        icon::DIAMOND
+    } else {
+        icon::DOLLAR
+    };
+    format!("{} {}", icon, bold(format_plain_price(amount, currency)))
+}
+
+fn format_plain_price(amount: i64, currency: &str) -> String {
+    let label = if currency == "diamonds" {
+        "diamantes"
+    } else {
+        "moedas"
+    };
+    format!("{} {label}", format_currency(amount))
+}
@@ -0,0 +1,55 @@
+use super::{
+    models::UserAchievementModel,
+    wallet::{self, WalletChange},
+};
+use chrono::Utc;
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+/// Unlocks an achievement and pays its reward in the same transaction.
+/// Returns `false` (and pays nothing) when the user already had it
+pub async fn unlock(
+    pool: &SqlitePool,
+    user_id: i32,
+    slug: &str,
+    reward: Option<WalletChange>,
+) -> Result<bool, SqlxError> {
+    let mut tx = pool.begin().await?;
+
+    let inserted = sqlx::query(
+        "INSERT INTO user_achievements (user_id, slug, unlocked_at) VALUES (?, ?, ?) \
+        ON CONFLICT(user_id, slug) DO NOTHING",
+    )
+    .bind(user_id)
+    .bind(slug)
+    .bind(Utc::now().to_rfc3339())
+    .execute(&mut *tx)
+    .await?
+    .rows_affected()
+        > 0;
+    if !inserted {
+        return Ok(false);
+    }
+
+    if let Some(change) = reward
+        && wallet::apply_in(&mut tx, user_id, &change).await?.is_none()
+    {
+        return Err(SqlxError::RowNotFound);
+    }
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// Lists a user's unlocked achievements, oldest first
+pub async fn list_by_user(
+    pool: &SqlitePool,
+    user_id: i32,
+) -> Result<Vec<UserAchievementModel>, SqlxError> {
+    sqlx::query_as::<_, UserAchievementModel>(
+        "SELECT slug, unlocked_at FROM user_achievements \
+        WHERE user_id = ? ORDER BY unlocked_at ASC, slug ASC",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
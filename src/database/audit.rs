@@ -0,0 +1,99 @@
+use super::{
+    models::{BalanceDriftModel, CurrencyTransactionModel, LedgerChainBreakModel},
+    transaction,
+    wallet::{Currency, WalletChange},
+};
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+/// Entries whose `balance_after` does not follow from the previous entry of the same
+/// user and currency (the first entry is expected to start from zero).
+/// Pass `user_id` to audit a single user
+pub async fn find_chain_breaks(
+    pool: &SqlitePool,
+    user_id: Option<i32>,
+    limit: i64,
+) -> Result<Vec<LedgerChainBreakModel>, SqlxError> {
+    sqlx::query_as::<_, LedgerChainBreakModel>(
+        "SELECT chain.id, chain.user_id, u.discord_id, chain.currency, chain.amount, \
+        chain.previous_balance, chain.balance_after \
+        FROM ( \
+            SELECT id, user_id, currency, amount, balance_after, \
+            COALESCE(LAG(balance_after) OVER ( \
+                PARTITION BY user_id, currency ORDER BY id \
+            ), 0) AS previous_balance \
+            FROM currency_transactions \
+            WHERE ? IS NULL OR user_id = ? \
+        ) chain \
+        JOIN users u ON u.id = chain.user_id \
+        WHERE chain.previous_balance + chain.amount != chain.balance_after \
+        ORDER BY chain.user_id, chain.currency, chain.id \
+        LIMIT ?",
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Users whose stored balance differs from the `balance_after` of their latest entry
+pub async fn find_balance_drifts(
+    pool: &SqlitePool,
+    user_id: Option<i32>,
+) -> Result<Vec<BalanceDriftModel>, SqlxError> {
+    sqlx::query_as::<_, BalanceDriftModel>(
+        "SELECT user_id, discord_id, currency, stored_balance, ledger_balance FROM ( \
+            SELECT u.id AS user_id, u.discord_id, 'dollars' AS currency, \
+            u.dollars AS stored_balance, \
+            COALESCE((SELECT t.balance_after FROM currency_transactions t \
+                WHERE t.user_id = u.id AND t.currency = 'dollars' \
+                ORDER BY t.id DESC LIMIT 1), 0) AS ledger_balance \
+            FROM users u \
+            UNION ALL \
+            SELECT u.id, u.discord_id, 'diamonds', u.diamonds, \
+            COALESCE((SELECT t.balance_after FROM currency_transactions t \
+                WHERE t.user_id = u.id AND t.currency = 'diamonds' \
+                ORDER BY t.id DESC LIMIT 1), 0) \
+            FROM users u \
+        ) \
+        WHERE stored_balance != ledger_balance AND (? IS NULL OR user_id = ?) \
+        ORDER BY user_id, currency",
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Appends a `reconciliation` entry so the ledger ends at the stored balance.
+/// The balance itself is left untouched; returns `None` when there is nothing to adjust
+pub async fn reconcile(
+    pool: &SqlitePool,
+    user_id: i32,
+    currency: Currency,
+    context: String,
+) -> Result<Option<CurrencyTransactionModel>, SqlxError> {
+    let mut tx = pool.begin().await?;
+
+    let stored_balance = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT {} FROM users WHERE id = ?",
+        currency.db_name()
+    ))
+    .bind(user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    let ledger_balance = transaction::latest_balance(&mut tx, user_id, currency.db_name())
+        .await?
+        .unwrap_or(0);
+
+    let drift = stored_balance - ledger_balance;
+    if drift == 0 {
+        return Ok(None);
+    }
+
+    let change = WalletChange::new(currency, drift, "reconciliation").with_context(context);
+    let entry = transaction::insert(&mut tx, &change.entry(user_id, stored_balance)).await?;
+    tx.commit().await?;
+
+    Ok(Some(entry))
+}
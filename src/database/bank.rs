@@ -0,0 +1,257 @@
+use super::{
+    models::{BankAccountModel, UserModel},
+    transaction::{self, NewTransaction},
+    wallet::{self, Currency, WalletChange},
+};
+use chrono::{DateTime, Utc};
+use sqlx::{Error as SqlxError, SqliteConnection, sqlite::SqlitePool};
+
+/// Ledger currency code used for savings entries
+pub const SAVINGS_CURRENCY: &str = "savings";
+
+const ACCOUNT_COLUMNS: &str = "id, user_id, balance, last_interest_at, created_at";
+
+/// Gets a user's account, opening an empty one if needed.
+/// `opened_at` is the daily reset new accounts start accruing from
+pub async fn get_or_open(
+    pool: &SqlitePool,
+    user_id: i32,
+    opened_at: DateTime<Utc>,
+) -> Result<BankAccountModel, SqlxError> {
+    let mut conn = pool.acquire().await?;
+    open_in(&mut conn, user_id, opened_at).await
+}
+
+async fn open_in(
+    conn: &mut SqliteConnection,
+    user_id: i32,
+    opened_at: DateTime<Utc>,
+) -> Result<BankAccountModel, SqlxError> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO bank_accounts (user_id, balance, last_interest_at, created_at) \
+        VALUES (?, 0, ?, ?)",
+    )
+    .bind(user_id)
+    .bind(opened_at.to_rfc3339())
+    .bind(Utc::now().to_rfc3339())
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query_as::<_, BankAccountModel>(&format!(
+        "SELECT {ACCOUNT_COLUMNS} FROM bank_accounts WHERE user_id = ?"
+    ))
+    .bind(user_id)
+    .fetch_one(&mut *conn)
+    .await
+}
+
+/// Adds `delta` to the savings balance unless it would go negative, and records it in the ledger
+async fn move_savings(
+    conn: &mut SqliteConnection,
+    user_id: i32,
+    delta: i64,
+    kind: &str,
+    context: Option<&str>,
+) -> Result<Option<(BankAccountModel, i32)>, SqlxError> {
+    let Some(account) = sqlx::query_as::<_, BankAccountModel>(&format!(
+        "UPDATE bank_accounts SET balance = balance + ? \
+        WHERE user_id = ? AND balance + ? >= 0 \
+        RETURNING {ACCOUNT_COLUMNS}"
+    ))
+    .bind(delta)
+    .bind(user_id)
+    .bind(delta)
+    .fetch_optional(&mut *conn)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let entry = transaction::insert(
+        &mut *conn,
+        &NewTransaction {
+            user_id,
+            amount: delta,
+            balance_after: account.balance,
+            currency: SAVINGS_CURRENCY,
+            kind,
+            context,
+            reversal_of: None,
+            actor_id: None,
+        },
+    )
+    .await?;
+
+    Ok(Some((account, entry.id)))
+}
+
+/// Result of moving dollars in or out of savings
+pub enum BankOutcome {
+    Completed {
+        user: UserModel,
+        account: BankAccountModel,
+    },
+    InsufficientFunds,
+}
+
+/// Moves dollars from the wallet into savings, writing a linked pair of `bank_deposit` entries
+pub async fn deposit(
+    pool: &SqlitePool,
+    user_id: i32,
+    amount: i64,
+    opened_at: DateTime<Utc>,
+) -> Result<BankOutcome, SqlxError> {
+    let mut tx = pool.begin().await?;
+    open_in(&mut tx, user_id, opened_at).await?;
+
+    let change = WalletChange::new(Currency::Dollars, -amount, "bank_deposit");
+    let Some(update) = wallet::apply_in(&mut tx, user_id, &change).await? else {
+        return Ok(BankOutcome::InsufficientFunds);
+    };
+    let Some((account, savings_entry)) =
+        move_savings(&mut tx, user_id, amount, "bank_deposit", None).await?
+    else {
+        return Err(SqlxError::RowNotFound);
+    };
+
+    transaction::link_counterparts(&mut tx, update.transaction.id, savings_entry).await?;
+    tx.commit().await?;
+
+    Ok(BankOutcome::Completed {
+        user: update.user,
+        account,
+    })
+}
+
+/// Moves dollars from savings back into the wallet, writing a linked pair of `bank_withdraw` entries
+pub async fn withdraw(
+    pool: &SqlitePool,
+    user_id: i32,
+    amount: i64,
+    opened_at: DateTime<Utc>,
+) -> Result<BankOutcome, SqlxError> {
+    let mut tx = pool.begin().await?;
+    open_in(&mut tx, user_id, opened_at).await?;
+
+    let Some((account, savings_entry)) =
+        move_savings(&mut tx, user_id, -amount, "bank_withdraw", None).await?
+    else {
+        return Ok(BankOutcome::InsufficientFunds);
+    };
+    let change = WalletChange::new(Currency::Dollars, amount, "bank_withdraw");
+    let Some(update) = wallet::apply_in(&mut tx, user_id, &change).await? else {
+        return Err(SqlxError::RowNotFound);
+    };
+
+    transaction::link_counterparts(&mut tx, savings_entry, update.transaction.id).await?;
+    tx.commit().await?;
+
+    Ok(BankOutcome::Completed {
+        user: update.user,
+        account,
+    })
+}
+
+/// Balance a day of savings earns interest on: the lowest balance between `from` (exclusive)
+/// and `until` (inclusive), read from the ledger. Interest already paid counts from the reset
+/// it was paid for rather than from when its entry was written, so a day earns the same
+/// whether its interest is paid live or caught up on later
+pub async fn earning_balance(
+    pool: &SqlitePool,
+    user_id: i32,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<i64, SqlxError> {
+    sqlx::query_scalar::<_, i64>(
+        "WITH savings AS ( \
+            SELECT id, created_at, balance_after - SUM( \
+                CASE WHEN kind = 'bank_interest' THEN amount ELSE 0 END \
+            ) OVER (ORDER BY id) AS principal \
+            FROM currency_transactions WHERE user_id = ? AND currency = ? \
+        ) \
+        SELECT MIN(principal) + ( \
+            SELECT COALESCE(SUM(amount), 0) FROM currency_transactions \
+            WHERE user_id = ? AND currency = ? AND kind = 'bank_interest' \
+        ) FROM ( \
+            SELECT COALESCE(( \
+                SELECT principal FROM savings WHERE julianday(created_at) <= julianday(?) \
+                ORDER BY id DESC LIMIT 1 \
+            ), 0) AS principal \
+            UNION ALL \
+            SELECT principal FROM savings \
+            WHERE julianday(created_at) > julianday(?) AND julianday(created_at) <= julianday(?) \
+        )",
+    )
+    .bind(user_id)
+    .bind(SAVINGS_CURRENCY)
+    .bind(user_id)
+    .bind(SAVINGS_CURRENCY)
+    .bind(from.to_rfc3339())
+    .bind(from.to_rfc3339())
+    .bind(until.to_rfc3339())
+    .fetch_one(pool)
+    .await
+}
+
+/// Accounts whose last paid reset is before `reset_at`
+pub async fn list_due(
+    pool: &SqlitePool,
+    reset_at: DateTime<Utc>,
+) -> Result<Vec<BankAccountModel>, SqlxError> {
+    sqlx::query_as::<_, BankAccountModel>(&format!(
+        "SELECT {ACCOUNT_COLUMNS} FROM bank_accounts \
+        WHERE julianday(last_interest_at) < julianday(?) \
+        ORDER BY id"
+    ))
+    .bind(reset_at.to_rfc3339())
+    .fetch_all(pool)
+    .await
+}
+
+/// Pays the interest for one daily reset. The account only advances if its
+/// `last_interest_at` still equals `previous_reset`, so running this twice for
+/// the same day is a no-op. Returns the updated account, or `None` when skipped
+pub async fn pay_interest(
+    pool: &SqlitePool,
+    account: &BankAccountModel,
+    reset_at: DateTime<Utc>,
+    amount: i64,
+) -> Result<Option<BankAccountModel>, SqlxError> {
+    let mut tx = pool.begin().await?;
+
+    let Some(updated) = sqlx::query_as::<_, BankAccountModel>(&format!(
+        "UPDATE bank_accounts SET balance = balance + ?, last_interest_at = ? \
+        WHERE id = ? AND last_interest_at = ? \
+        RETURNING {ACCOUNT_COLUMNS}"
+    ))
+    .bind(amount)
+    .bind(reset_at.to_rfc3339())
+    .bind(account.id)
+    .bind(&account.last_interest_at)
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    if amount > 0 {
+        let context = format!("interest:{}", reset_at.format("%Y-%m-%d"));
+        transaction::insert(
+            &mut tx,
+            &NewTransaction {
+                user_id: updated.user_id,
+                amount,
+                balance_after: updated.balance,
+                currency: SAVINGS_CURRENCY,
+                kind: "bank_interest",
+                context: Some(&context),
+                reversal_of: None,
+                actor_id: None,
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(Some(updated))
+}
@@ -0,0 +1,168 @@
+use super::models::GamblingLimitsModel;
+use chrono::{DateTime, Utc};
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+const LIMIT_COLUMNS: &str = "user_id, daily_limit, weekly_limit, pending_daily_limit, \
+    pending_daily_at, pending_weekly_limit, pending_weekly_at, excluded_until";
+
+/// Ledger kinds that count towards gambling losses: stakes, their refunds and payouts.
+/// New betting games should add their kinds here
+pub const GAMBLING_KINDS: &[&str] = &[
+    "mines_wager",
+    "mines_refund",
+    "mines_cashout",
+    "mines_autocashout",
+    "jokenpo_wager",
+    "jokenpo_refund",
+    "jokenpo_payout",
+    "jokenpo_tournament_entry",
+    "jokenpo_tournament_refund",
+    "jokenpo_tournament_prize",
+];
+
+/// Limit period a change applies to
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LimitPeriod {
+    Daily,
+    Weekly,
+}
+
+impl LimitPeriod {
+    fn columns(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Self::Daily => ("daily_limit", "pending_daily_limit", "pending_daily_at"),
+            Self::Weekly => ("weekly_limit", "pending_weekly_limit", "pending_weekly_at"),
+        }
+    }
+
+    pub fn current(self, limits: &GamblingLimitsModel) -> Option<i64> {
+        match self {
+            Self::Daily => limits.daily_limit,
+            Self::Weekly => limits.weekly_limit,
+        }
+    }
+}
+
+/// Gets the user's limits after promoting any pending change whose cooling-off has passed
+pub async fn get(
+    pool: &SqlitePool,
+    user_id: i32,
+    now: DateTime<Utc>,
+) -> Result<GamblingLimitsModel, SqlxError> {
+    let mut tx = pool.begin().await?;
+
+    for period in [LimitPeriod::Daily, LimitPeriod::Weekly] {
+        let (limit, pending, pending_at) = period.columns();
+        sqlx::query(&format!(
+            "UPDATE gambling_limits SET {limit} = {pending}, {pending} = NULL, {pending_at} = NULL \
+            WHERE user_id = ? AND {pending_at} IS NOT NULL \
+            AND julianday({pending_at}) <= julianday(?)"
+        ))
+        .bind(user_id)
+        .bind(now.to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let limits = sqlx::query_as::<_, GamblingLimitsModel>(&format!(
+        "SELECT {LIMIT_COLUMNS} FROM gambling_limits WHERE user_id = ?"
+    ))
+    .bind(user_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(limits.unwrap_or(GamblingLimitsModel {
+        user_id,
+        ..Default::default()
+    }))
+}
+
+/// Replaces a limit right away and drops any pending change for that period
+pub async fn set_limit_now(
+    pool: &SqlitePool,
+    user_id: i32,
+    period: LimitPeriod,
+    limit: Option<i64>,
+) -> Result<GamblingLimitsModel, SqlxError> {
+    let (column, pending, pending_at) = period.columns();
+    sqlx::query_as::<_, GamblingLimitsModel>(&format!(
+        "INSERT INTO gambling_limits (user_id, {column}, updated_at) VALUES (?1, ?2, ?3) \
+        ON CONFLICT(user_id) DO UPDATE SET \
+            {column} = ?2, {pending} = NULL, {pending_at} = NULL, updated_at = ?3 \
+        RETURNING {LIMIT_COLUMNS}"
+    ))
+    .bind(user_id)
+    .bind(limit)
+    .bind(Utc::now().to_rfc3339())
+    .fetch_one(pool)
+    .await
+}
+
+/// Schedules a limit to replace the current one at `effective_at`
+pub async fn schedule_limit(
+    pool: &SqlitePool,
+    user_id: i32,
+    period: LimitPeriod,
+    limit: Option<i64>,
+    effective_at: DateTime<Utc>,
+) -> Result<GamblingLimitsModel, SqlxError> {
+    let (_, pending, pending_at) = period.columns();
+    sqlx::query_as::<_, GamblingLimitsModel>(&format!(
+        "INSERT INTO gambling_limits (user_id, {pending}, {pending_at}, updated_at) \
+        VALUES (?1, ?2, ?3, ?4) \
+        ON CONFLICT(user_id) DO UPDATE SET \
+            {pending} = ?2, {pending_at} = ?3, updated_at = ?4 \
+        RETURNING {LIMIT_COLUMNS}"
+    ))
+    .bind(user_id)
+    .bind(limit)
+    .bind(effective_at.to_rfc3339())
+    .bind(Utc::now().to_rfc3339())
+    .fetch_one(pool)
+    .await
+}
+
+/// Excludes the user from gambling until `until`. An existing longer exclusion is kept
+pub async fn exclude_until(
+    pool: &SqlitePool,
+    user_id: i32,
+    until: DateTime<Utc>,
+) -> Result<GamblingLimitsModel, SqlxError> {
+    sqlx::query_as::<_, GamblingLimitsModel>(&format!(
+        "INSERT INTO gambling_limits (user_id, excluded_until, updated_at) VALUES (?1, ?2, ?3) \
+        ON CONFLICT(user_id) DO UPDATE SET \
+            excluded_until = CASE \
+                WHEN excluded_until IS NOT NULL \
+                    AND julianday(excluded_until) > julianday(?2) THEN excluded_until \
+                ELSE ?2 END, \
+            updated_at = ?3 \
+        RETURNING {LIMIT_COLUMNS}"
+    ))
+    .bind(user_id)
+    .bind(until.to_rfc3339())
+    .bind(Utc::now().to_rfc3339())
+    .fetch_one(pool)
+    .await
+}
+
+/// Net amount lost in gambling since `since` (0 when the user is ahead)
+pub async fn net_loss_since(
+    pool: &SqlitePool,
+    user_id: i32,
+    since: DateTime<Utc>,
+) -> Result<i64, SqlxError> {
+    let placeholders = vec!["?"; GAMBLING_KINDS.len()].join(", ");
+    let sql = format!(
+        "SELECT COALESCE(SUM(amount), 0) FROM currency_transactions \
+        WHERE user_id = ? AND currency = 'dollars' AND kind IN ({placeholders}) \
+        AND julianday(created_at) >= julianday(?)"
+    );
+
+    let mut query = sqlx::query_scalar::<_, i64>(&sql).bind(user_id);
+    for kind in GAMBLING_KINDS {
+        query = query.bind(*kind);
+    }
+    let net = query.bind(since.to_rfc3339()).fetch_one(pool).await?;
+    Ok((-net).max(0))
+}
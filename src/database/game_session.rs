@@ -0,0 +1,241 @@
+use super::{
+    models::GameSessionModel,
+    wallet::{self, WalletChange, WalletUpdate},
+};
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqliteConnection, sqlite::SqlitePool};
+
+/// Debits the wager and opens the session that tracks it in one transaction.
+/// Returns `None` (and writes nothing) when the balance cannot cover the wager
+pub async fn start(
+    pool: &SqlitePool,
+    game: &str,
+    user_id: i32,
+    wager: WalletChange,
+    layout: &str,
+) -> Result<Option<(WalletUpdate, i32)>, SqlxError> {
+    let mut tx = pool.begin().await?;
+    let Some(started) = start_in(&mut tx, game, user_id, &wager, layout).await? else {
+        return Ok(None);
+    };
+
+    tx.commit().await?;
+    Ok(Some(started))
+}
+
+/// Debits every player's stake and opens one session per player in a single transaction,
+/// so a multiplayer pot is escrowed all at once. Returns the session IDs in `stakes` order,
+/// or `None` (and writes nothing) when any balance cannot cover its stake
+pub async fn start_group(
+    pool: &SqlitePool,
+    game: &str,
+    stakes: &[(i32, WalletChange)],
+) -> Result<Option<Vec<i32>>, SqlxError> {
+    let mut tx = pool.begin().await?;
+    let mut session_ids = Vec::with_capacity(stakes.len());
+    for (user_id, wager) in stakes {
+        let Some((_, session_id)) = start_in(&mut tx, game, *user_id, wager, "").await? else {
+            return Ok(None);
+        };
+        session_ids.push(session_id);
+    }
+
+    tx.commit().await?;
+    Ok(Some(session_ids))
+}
+
+async fn start_in(
+    conn: &mut SqliteConnection,
+    game: &str,
+    user_id: i32,
+    wager: &WalletChange,
+    layout: &str,
+) -> Result<Option<(WalletUpdate, i32)>, SqlxError> {
+    let Some(update) = wallet::apply_in(&mut *conn, user_id, wager).await? else {
+        return Ok(None);
+    };
+
+    let now = Utc::now().to_rfc3339();
+    let session_id = sqlx::query_scalar::<_, i32>(
+        "INSERT INTO game_sessions \
+        (game, user_id, wager, wager_transaction_id, layout, revealed, created_at, updated_at) \
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?) \
+        RETURNING id",
+    )
+    .bind(game)
+    .bind(user_id)
+    .bind(-wager.amount)
+    .bind(update.transaction.id)
+    .bind(layout)
+    .bind("0".repeat(layout.len()))
+    .bind(&now)
+    .bind(&now)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Some((update, session_id)))
+}
+
+/// Records the message the game is played on
+pub async fn attach_message(
+    pool: &SqlitePool,
+    session_id: i32,
+    channel_id: i64,
+    message_id: i64,
+) -> Result<(), SqlxError> {
+    sqlx::query(
+        "UPDATE game_sessions SET channel_id = ?, message_id = ?, updated_at = ? WHERE id = ?",
+    )
+    .bind(channel_id)
+    .bind(message_id)
+    .bind(Utc::now().to_rfc3339())
+    .bind(session_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Saves which tiles are open
+pub async fn record_reveal(
+    pool: &SqlitePool,
+    session_id: i32,
+    revealed: &str,
+) -> Result<(), SqlxError> {
+    sqlx::query(
+        "UPDATE game_sessions SET revealed = ?, updated_at = ? \
+        WHERE id = ? AND status = 'active'",
+    )
+    .bind(revealed)
+    .bind(Utc::now().to_rfc3339())
+    .bind(session_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Marks an active session as finished with `status`, applying the payout (if any)
+/// in the same transaction. Returns `None` when the session was already closed
+pub async fn close(
+    pool: &SqlitePool,
+    session_id: i32,
+    status: &str,
+    payout: Option<WalletChange>,
+) -> Result<Option<Option<WalletUpdate>>, SqlxError> {
+    let mut tx = pool.begin().await?;
+    let Some(update) = close_in(&mut tx, session_id, status, payout.as_ref()).await? else {
+        return Ok(None);
+    };
+
+    tx.commit().await?;
+    Ok(Some(update))
+}
+
+/// How one session of a group is settled
+pub struct SessionClose<'a> {
+    pub session_id: i32,
+    pub status: &'a str,
+    pub payout: Option<WalletChange>,
+}
+
+/// Closes several sessions together, e.g. every seat of a multiplayer pot.
+/// Returns `None` (and writes nothing) when any of them was already closed
+pub async fn close_group(
+    pool: &SqlitePool,
+    closes: &[SessionClose<'_>],
+) -> Result<Option<Vec<Option<WalletUpdate>>>, SqlxError> {
+    let mut tx = pool.begin().await?;
+    let mut updates = Vec::with_capacity(closes.len());
+    for close in closes {
+        let Some(update) = close_in(
+            &mut tx,
+            close.session_id,
+            close.status,
+            close.payout.as_ref(),
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+        updates.push(update);
+    }
+
+    tx.commit().await?;
+    Ok(Some(updates))
+}
+
+async fn close_in(
+    conn: &mut SqliteConnection,
+    session_id: i32,
+    status: &str,
+    payout: Option<&WalletChange>,
+) -> Result<Option<Option<WalletUpdate>>, SqlxError> {
+    let Some(user_id) = mark_closed(&mut *conn, session_id, status).await? else {
+        return Ok(None);
+    };
+
+    let update = match payout {
+        Some(change) => wallet::apply_in(&mut *conn, user_id, change).await?,
+        None => None,
+    };
+    Ok(Some(update))
+}
+
+/// Marks an active session as finished with `status` and reverses its wager.
+/// Returns `None` when the session was already closed or the wager was already reversed
+pub async fn close_with_refund(
+    pool: &SqlitePool,
+    session_id: i32,
+    status: &str,
+    kind: &'static str,
+    context: impl Into<String>,
+) -> Result<Option<WalletUpdate>, SqlxError> {
+    let mut tx = pool.begin().await?;
+    let Some(user_id) = mark_closed(&mut tx, session_id, status).await? else {
+        return Ok(None);
+    };
+
+    let wager_transaction_id =
+        sqlx::query_scalar::<_, i32>("SELECT wager_transaction_id FROM game_sessions WHERE id = ?")
+            .bind(session_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+    let update = wallet::revert_in(&mut tx, user_id, wager_transaction_id, kind, context).await?;
+    tx.commit().await?;
+    Ok(update)
+}
+
+async fn mark_closed(
+    conn: &mut SqliteConnection,
+    session_id: i32,
+    status: &str,
+) -> Result<Option<i32>, SqlxError> {
+    sqlx::query_scalar::<_, i32>(
+        "UPDATE game_sessions SET status = ?, updated_at = ? \
+        WHERE id = ? AND status = 'active' \
+        RETURNING user_id",
+    )
+    .bind(status)
+    .bind(Utc::now().to_rfc3339())
+    .bind(session_id)
+    .fetch_optional(&mut *conn)
+    .await
+}
+
+/// Sessions of a game that were never closed, oldest first
+pub async fn list_active(
+    pool: &SqlitePool,
+    game: &str,
+) -> Result<Vec<GameSessionModel>, SqlxError> {
+    sqlx::query_as::<_, GameSessionModel>(
+        "SELECT gs.id, gs.game, gs.user_id, u.discord_id, gs.channel_id, gs.message_id, \
+        gs.wager, gs.wager_transaction_id, gs.layout, gs.revealed, gs.status, gs.created_at \
+        FROM game_sessions gs \
+        JOIN users u ON u.id = gs.user_id \
+        WHERE gs.game = ? AND gs.status = 'active' \
+        ORDER BY gs.id ASC",
+    )
+    .bind(game)
+    .fetch_all(pool)
+    .await
+}
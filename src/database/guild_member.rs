@@ -0,0 +1,22 @@
+use chrono::Utc;
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+/// Marks a user as seen in a guild, creating the membership on first sight
+pub async fn record(pool: &SqlitePool, user_id: i32, guild_id: i64) -> Result<(), SqlxError> {
+    let now = Utc::now().to_rfc3339();
+
+    sqlx::query(
+        "INSERT INTO guild_members (user_id, guild_id, first_seen_at, last_seen_at)
+         VALUES (?, ?, ?, ?)
+         ON CONFLICT(user_id, guild_id) DO UPDATE SET
+            last_seen_at = excluded.last_seen_at",
+    )
+    .bind(user_id)
+    .bind(guild_id)
+    .bind(&now)
+    .bind(&now)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
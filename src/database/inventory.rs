@@ -0,0 +1,20 @@
+use super::models::InventoryEntryModel;
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+/// Lists the items a user owns, most recently acquired first
+pub async fn list_by_user(
+    pool: &SqlitePool,
+    user_id: i32,
+) -> Result<Vec<InventoryEntryModel>, SqlxError> {
+    sqlx::query_as::<_, InventoryEntryModel>(
+        "SELECT s.id AS item_id, s.slug, s.name, s.description, s.kind, i.quantity, \
+        i.acquired_at \
+        FROM inventory i \
+        JOIN shop_items s ON s.id = i.item_id \
+        WHERE i.user_id = ? AND i.quantity > 0 \
+        ORDER BY i.acquired_at DESC, s.id ASC",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
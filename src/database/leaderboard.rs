@@ -0,0 +1,91 @@
+use super::models::LeaderboardEntryModel;
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+/// Value used to order the ranking
+#[derive(Clone, Copy, Debug)]
+pub enum LeaderboardMetric {
+    Dollars,
+    Diamonds,
+    RewardClaims,
+}
+
+impl LeaderboardMetric {
+    fn value_sql(self) -> &'static str {
+        match self {
+            Self::Dollars => "u.dollars",
+            Self::Diamonds => "u.diamonds",
+            Self::RewardClaims => {
+                "COALESCE((SELECT SUM(rs.total_claims) FROM reward_states rs \
+                WHERE rs.user_id = u.id), 0)"
+            }
+        }
+    }
+
+    /// Whether the metric exposes a balance, so users hiding theirs must be left out
+    pub fn is_balance(self) -> bool {
+        matches!(self, Self::Dollars | Self::Diamonds)
+    }
+}
+
+/// Builds the ranked selection, optionally restricted to members seen in a guild.
+/// Users with nothing to show (value 0) are left out, as are users hiding their balance
+/// when ranking by one
+fn ranked_sql(metric: LeaderboardMetric, scoped: bool) -> String {
+    let membership = if scoped {
+        "JOIN guild_members gm ON gm.user_id = u.id AND gm.guild_id = ?"
+    } else {
+        ""
+    };
+    let privacy = if metric.is_balance() {
+        "AND NOT EXISTS (SELECT 1 FROM user_settings us \
+            WHERE us.user_id = u.id AND us.hide_balance = 1)"
+    } else {
+        ""
+    };
+
+    format!(
+        "SELECT u.id AS user_id, u.discord_id, {value} AS value, \
+        ROW_NUMBER() OVER (ORDER BY {value} DESC, u.id ASC) AS position \
+        FROM users u {membership} \
+        WHERE {value} > 0 {privacy}",
+        value = metric.value_sql(),
+    )
+}
+
+/// Returns the top users for a metric, globally or within a guild
+pub async fn top(
+    pool: &SqlitePool,
+    metric: LeaderboardMetric,
+    guild_id: Option<i64>,
+    limit: i64,
+) -> Result<Vec<LeaderboardEntryModel>, SqlxError> {
+    let sql = format!(
+        "SELECT user_id, discord_id, value, position FROM ({}) ORDER BY position LIMIT ?",
+        ranked_sql(metric, guild_id.is_some())
+    );
+
+    let mut query = sqlx::query_as::<_, LeaderboardEntryModel>(&sql);
+    if let Some(guild_id) = guild_id {
+        query = query.bind(guild_id);
+    }
+    query.bind(limit).fetch_all(pool).await
+}
+
+/// Finds a single user's entry in the ranking, if they have one
+pub async fn find_position(
+    pool: &SqlitePool,
+    metric: LeaderboardMetric,
+    guild_id: Option<i64>,
+    user_id: i32,
+) -> Result<Option<LeaderboardEntryModel>, SqlxError> {
+    let sql = format!(
+        "SELECT user_id, discord_id, value, position FROM ({}) WHERE user_id = ?",
+        ranked_sql(metric, guild_id.is_some())
+    );
+
+    let mut query = sqlx::query_as::<_, LeaderboardEntryModel>(&sql);
+    if let Some(guild_id) = guild_id {
+        query = query.bind(guild_id);
+    }
+    query.bind(user_id).fetch_optional(pool).await
+}
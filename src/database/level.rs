@@ -0,0 +1,115 @@
+use super::{
+    models::{LevelEntryModel, MemberLevelModel},
+    wallet::{self, WalletChange},
+};
+use chrono::{DateTime, Utc};
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+const LEVEL_COLUMNS: &str = "guild_id, user_id, xp, level, last_message_at";
+
+/// Adds message XP unless the member earned XP less than `cooldown_secs` ago.
+/// Returns the updated row, or `None` while the cooldown is still running
+pub async fn add_xp(
+    pool: &SqlitePool,
+    guild_id: i64,
+    user_id: i32,
+    amount: i64,
+    now: DateTime<Utc>,
+    cooldown_secs: i64,
+) -> Result<Option<MemberLevelModel>, SqlxError> {
+    sqlx::query_as::<_, MemberLevelModel>(&format!(
+        "INSERT INTO member_levels (guild_id, user_id, xp, level, last_message_at) \
+        VALUES (?, ?, ?, 0, ?) \
+        ON CONFLICT(guild_id, user_id) DO UPDATE SET \
+            xp = member_levels.xp + excluded.xp, \
+            last_message_at = excluded.last_message_at \
+        WHERE CAST(strftime('%s', excluded.last_message_at) AS INTEGER) \
+            - CAST(strftime('%s', member_levels.last_message_at) AS INTEGER) >= ? \
+        RETURNING {LEVEL_COLUMNS}"
+    ))
+    .bind(guild_id)
+    .bind(user_id)
+    .bind(amount)
+    .bind(now.to_rfc3339())
+    .bind(cooldown_secs)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Moves the stored level from `from_level` to `to_level` and pays the reward in the same
+/// transaction. Returns `false` when another message already handled this level-up
+pub async fn level_up(
+    pool: &SqlitePool,
+    guild_id: i64,
+    user_id: i32,
+    from_level: i64,
+    to_level: i64,
+    reward: Option<WalletChange>,
+) -> Result<bool, SqlxError> {
+    let mut tx = pool.begin().await?;
+
+    let updated = sqlx::query(
+        "UPDATE member_levels SET level = ? \
+        WHERE guild_id = ? AND user_id = ? AND level = ?",
+    )
+    .bind(to_level)
+    .bind(guild_id)
+    .bind(user_id)
+    .bind(from_level)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected()
+        > 0;
+    if !updated {
+        return Ok(false);
+    }
+
+    if let Some(change) = reward
+        && wallet::apply_in(&mut tx, user_id, &change).await?.is_none()
+    {
+        return Err(SqlxError::RowNotFound);
+    }
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+fn ranked_sql() -> &'static str {
+    "SELECT ml.user_id, u.discord_id, ml.xp, ml.level, \
+    ROW_NUMBER() OVER (ORDER BY ml.xp DESC, ml.user_id ASC) AS position \
+    FROM member_levels ml \
+    JOIN users u ON u.id = ml.user_id \
+    WHERE ml.guild_id = ? AND ml.xp > 0"
+}
+
+/// Top members of a guild by XP
+pub async fn top(
+    pool: &SqlitePool,
+    guild_id: i64,
+    limit: i64,
+) -> Result<Vec<LevelEntryModel>, SqlxError> {
+    sqlx::query_as::<_, LevelEntryModel>(&format!(
+        "SELECT user_id, discord_id, xp, level, position FROM ({}) ORDER BY position LIMIT ?",
+        ranked_sql()
+    ))
+    .bind(guild_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// A member's XP and position within a guild, if they have earned any
+pub async fn find_position(
+    pool: &SqlitePool,
+    guild_id: i64,
+    user_id: i32,
+) -> Result<Option<LevelEntryModel>, SqlxError> {
+    sqlx::query_as::<_, LevelEntryModel>(&format!(
+        "SELECT user_id, discord_id, xp, level, position FROM ({}) WHERE user_id = ?",
+        ranked_sql()
+    ))
+    .bind(guild_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+}
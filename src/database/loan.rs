@@ -0,0 +1,242 @@
+use super::{
+    models::{LoanModel, UserModel},
+    transaction::{self, NewTransaction},
+    wallet::{self, Currency, WalletChange},
+};
+use chrono::{DateTime, Utc};
+use sqlx::{Error as SqlxError, SqliteConnection, sqlite::SqlitePool};
+
+const LOAN_COLUMNS: &str = "id, user_id, principal, interest, amount_due, amount_repaid, status, \
+    created_at, due_at, repaid_at";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoanStatus {
+    Active,
+    Repaid,
+    Defaulted,
+}
+
+impl LoanStatus {
+    pub fn from_db_name(name: &str) -> Option<Self> {
+        match name {
+            "active" => Some(Self::Active),
+            "repaid" => Some(Self::Repaid),
+            "defaulted" => Some(Self::Defaulted),
+            _ => None,
+        }
+    }
+}
+
+impl LoanModel {
+    pub fn status(&self) -> Option<LoanStatus> {
+        LoanStatus::from_db_name(&self.status)
+    }
+
+    pub fn outstanding(&self) -> i64 {
+        (self.amount_due - self.amount_repaid).max(0)
+    }
+
+    pub fn due_datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.due_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+}
+
+/// Total reward claims across every reward type, used to size the borrowing limit
+pub async fn total_reward_claims(pool: &SqlitePool, user_id: i32) -> Result<i64, SqlxError> {
+    sqlx::query_scalar::<_, i64>(
+        "SELECT COALESCE(SUM(total_claims), 0) FROM reward_states WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+}
+
+/// The user's active or defaulted loan, if any. An active loan past its due date
+/// is marked as defaulted on the way out
+pub async fn find_open(
+    pool: &SqlitePool,
+    user_id: i32,
+    now: DateTime<Utc>,
+) -> Result<Option<LoanModel>, SqlxError> {
+    let Some(loan) = sqlx::query_as::<_, LoanModel>(&format!(
+        "SELECT {LOAN_COLUMNS} FROM loans \
+        WHERE user_id = ? AND status IN ('active', 'defaulted')"
+    ))
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let overdue = loan.status() == Some(LoanStatus::Active)
+        && loan.due_datetime().is_some_and(|due_at| now > due_at);
+    if !overdue {
+        return Ok(Some(loan));
+    }
+
+    Ok(mark_defaulted(pool, &loan).await?.or(Some(loan)))
+}
+
+/// Flags an overdue loan and records the default as a zero-amount `loan_default` entry
+async fn mark_defaulted(
+    pool: &SqlitePool,
+    loan: &LoanModel,
+) -> Result<Option<LoanModel>, SqlxError> {
+    let mut tx = pool.begin().await?;
+
+    let Some(updated) = sqlx::query_as::<_, LoanModel>(&format!(
+        "UPDATE loans SET status = 'defaulted' WHERE id = ? AND status = 'active' \
+        RETURNING {LOAN_COLUMNS}"
+    ))
+    .bind(loan.id)
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let dollars = sqlx::query_scalar::<_, i64>("SELECT dollars FROM users WHERE id = ?")
+        .bind(loan.user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    let context = loan_context(loan.id);
+    transaction::insert(
+        &mut tx,
+        &NewTransaction {
+            user_id: loan.user_id,
+            amount: 0,
+            balance_after: dollars,
+            currency: Currency::Dollars.db_name(),
+            kind: "loan_default",
+            context: Some(&context),
+            reversal_of: None,
+            actor_id: None,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+    Ok(Some(updated))
+}
+
+/// Opens a loan and credits the principal as a `loan_disbursement` entry.
+/// Returns `None` when the user already has an unpaid loan
+pub async fn take(
+    pool: &SqlitePool,
+    user_id: i32,
+    principal: i64,
+    interest: i64,
+    due_at: DateTime<Utc>,
+) -> Result<Option<(UserModel, LoanModel)>, SqlxError> {
+    let mut tx = pool.begin().await?;
+
+    let inserted = sqlx::query_as::<_, LoanModel>(&format!(
+        "INSERT INTO loans (user_id, principal, interest, amount_due, created_at, due_at) \
+        VALUES (?, ?, ?, ?, ?, ?) \
+        ON CONFLICT DO NOTHING \
+        RETURNING {LOAN_COLUMNS}"
+    ))
+    .bind(user_id)
+    .bind(principal)
+    .bind(interest)
+    .bind(principal + interest)
+    .bind(Utc::now().to_rfc3339())
+    .bind(due_at.to_rfc3339())
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(loan) = inserted else {
+        return Ok(None);
+    };
+
+    let change = WalletChange::new(Currency::Dollars, principal, "loan_disbursement")
+        .with_context(loan_context(loan.id));
+    let Some(update) = wallet::apply_in(&mut tx, user_id, &change).await? else {
+        return Err(SqlxError::RowNotFound);
+    };
+
+    tx.commit().await?;
+    Ok(Some((update.user, loan)))
+}
+
+/// Outcome of a successful repayment
+pub struct Repayment {
+    pub user: UserModel,
+    pub loan: LoanModel,
+    pub amount: i64,
+}
+
+/// Pays up to `max_amount` of the user's open loan from their wallet, limited by the
+/// outstanding amount and the current balance. Returns `None` when nothing could be paid
+pub async fn repay(
+    pool: &SqlitePool,
+    user_id: i32,
+    max_amount: i64,
+    kind: &'static str,
+) -> Result<Option<Repayment>, SqlxError> {
+    let mut tx = pool.begin().await?;
+    let repayment = repay_in(&mut tx, user_id, max_amount, kind).await?;
+    if repayment.is_some() {
+        tx.commit().await?;
+    }
+    Ok(repayment)
+}
+
+async fn repay_in(
+    conn: &mut SqliteConnection,
+    user_id: i32,
+    max_amount: i64,
+    kind: &'static str,
+) -> Result<Option<Repayment>, SqlxError> {
+    let Some(loan) = sqlx::query_as::<_, LoanModel>(&format!(
+        "SELECT {LOAN_COLUMNS} FROM loans \
+        WHERE user_id = ? AND status IN ('active', 'defaulted')"
+    ))
+    .bind(user_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let dollars = sqlx::query_scalar::<_, i64>("SELECT dollars FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    let amount = max_amount.min(loan.outstanding()).min(dollars);
+    if amount <= 0 {
+        return Ok(None);
+    }
+
+    let change =
+        WalletChange::new(Currency::Dollars, -amount, kind).with_context(loan_context(loan.id));
+    let Some(update) = wallet::apply_in(&mut *conn, user_id, &change).await? else {
+        return Ok(None);
+    };
+
+    let loan = sqlx::query_as::<_, LoanModel>(&format!(
+        "UPDATE loans SET \
+            amount_repaid = amount_repaid + ?1, \
+            status = CASE WHEN amount_repaid + ?1 >= amount_due THEN 'repaid' ELSE status END, \
+            repaid_at = CASE WHEN amount_repaid + ?1 >= amount_due THEN ?2 ELSE repaid_at END \
+        WHERE id = ?3 \
+        RETURNING {LOAN_COLUMNS}"
+    ))
+    .bind(amount)
+    .bind(Utc::now().to_rfc3339())
+    .bind(loan.id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Some(Repayment {
+        user: update.user,
+        loan,
+        amount,
+    }))
+}
+
+fn loan_context(loan_id: i32) -> String {
+    format!("loan:{loan_id}")
+}
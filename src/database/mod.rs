@@ -1,8 +1,23 @@
+pub mod achievement;
+pub mod audit;
+pub mod bank;
 pub mod blacklist;
+pub mod gambling;
+pub mod game_session;
+pub mod guild_member;
+pub mod inventory;
+pub mod leaderboard;
+pub mod level;
+pub mod loan;
 pub mod models;
+pub mod profile;
 pub mod reward;
+pub mod settings;
+pub mod shop;
+pub mod stats;
 pub mod transaction;
 pub mod user;
+pub mod wallet;
 
 use crate::env;
 use sqlx::{Error as SqlxError, sqlite::SqlitePool};
@@ -21,15 +36,20 @@ pub async fn connect() -> Result<SqlitePool, SqlxError> {
     Ok(pool)
 }
 
-pub use models::{BlacklistEntryModel, CurrencyTransactionModel, RewardStateModel, UserModel};
+pub use models::{
+    BalanceDriftModel, BankAccountModel, BlacklistEntryModel, CurrencyTransactionModel,
+    EconomyWindowModel, GamblingLimitsModel, GameSessionModel, InventoryEntryModel, KindEntryModel,
+    LeaderboardEntryModel, LedgerChainBreakModel, LevelEntryModel, LoanModel, MemberLevelModel,
+    RewardStateModel, ShopItemModel, TransactionTotalsModel, UserAchievementModel, UserModel,
+};
 
 pub use blacklist::{
     delete_by_discord_id as delete_blacklist_entry, find_by_discord_id as find_blacklist_entry,
     insert as insert_blacklist_entry, list_recent as list_blacklist_entries,
 };
-pub use reward::{get_all as get_all_reward_states, upsert as upsert_reward_state};
-pub use transaction::{
-    delete_by_id as delete_currency_transaction, insert as insert_currency_transaction,
-    list_recent_by_user as list_currency_transactions,
-};
-pub use user::{get_or_create as get_or_create_user, update_balance as update_user_balance};
+pub use guild_member::record as record_guild_member;
+pub use leaderboard::LeaderboardMetric;
+pub use reward::get_all as get_all_reward_states;
+pub use transaction::{TransactionFilter, TransactionSign};
+pub use user::get_or_create as get_or_create_user;
+pub use wallet::{Currency, WalletChange};
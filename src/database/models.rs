@@ -20,6 +20,49 @@ pub struct CurrencyTransactionModel {
     pub kind: String,
     pub context: Option<String>,
     pub created_at: String,
+    pub counterpart_id: Option<i32>,
+    pub reversal_of: Option<i32>,
+    pub actor_id: Option<i64>,
+}
+
+/// Ledger entry joined with its owner's Discord ID, used for cross-user listings
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct KindEntryModel {
+    pub id: i32,
+    pub discord_id: i64,
+    pub amount: i64,
+    pub balance_after: i64,
+    pub currency: String,
+    pub context: Option<String>,
+    pub actor_id: Option<i64>,
+    pub created_at: String,
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct TransactionTotalsModel {
+    pub currency: String,
+    pub count: i64,
+    pub net: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct LedgerChainBreakModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub discord_id: i64,
+    pub currency: String,
+    pub amount: i64,
+    pub previous_balance: i64,
+    pub balance_after: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct BalanceDriftModel {
+    pub user_id: i32,
+    pub discord_id: i64,
+    pub currency: String,
+    pub stored_balance: i64,
+    pub ledger_balance: i64,
 }
 
 #[derive(Clone, Debug, PartialEq, FromRow)]
@@ -30,6 +73,7 @@ pub struct RewardStateModel {
     pub last_claimed_at: Option<String>,
     pub next_reset_at: Option<String>,
     pub total_claims: i64,
+    pub streak: i64,
 }
 
 impl RewardStateModel {
@@ -49,3 +93,164 @@ pub struct BlacklistEntryModel {
     pub reason: Option<String>,
     pub created_at: String,
 }
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct LeaderboardEntryModel {
+    pub user_id: i32,
+    pub discord_id: i64,
+    pub value: i64,
+    pub position: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct ShopItemModel {
+    pub id: i32,
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    pub price: i64,
+    pub currency: String,
+    pub kind: String,
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct InventoryEntryModel {
+    pub item_id: i32,
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    pub kind: String,
+    pub quantity: i64,
+    pub acquired_at: String,
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct BankAccountModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub balance: i64,
+    pub last_interest_at: String,
+    pub created_at: String,
+}
+
+impl BankAccountModel {
+    pub fn last_interest_datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.last_interest_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct LoanModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub principal: i64,
+    pub interest: i64,
+    pub amount_due: i64,
+    pub amount_repaid: i64,
+    pub status: String,
+    pub created_at: String,
+    pub due_at: String,
+    pub repaid_at: Option<String>,
+}
+
+/// Ledger aggregates for one statistics window
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct EconomyWindowModel {
+    pub minted_dollars: i64,
+    pub minted_diamonds: i64,
+    pub mines_wagered: i64,
+    pub mines_paid_out: i64,
+    pub net_dollars: i64,
+    pub net_diamonds: i64,
+    pub active_users: i64,
+}
+
+impl EconomyWindowModel {
+    /// What the house kept from mines: positive when players lost overall
+    pub fn mines_house_net(&self) -> i64 {
+        self.mines_wagered - self.mines_paid_out
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct EconomySupplyModel {
+    pub users: i64,
+    pub dollars: i64,
+    pub diamonds: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct UserSettingsModel {
+    pub user_id: i32,
+    pub hide_balance: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct GameStatsModel {
+    pub mines_played: i64,
+    pub biggest_cashout: i64,
+    pub mines_net: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct UserAchievementModel {
+    pub slug: String,
+    pub unlocked_at: String,
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct MemberLevelModel {
+    pub guild_id: i64,
+    pub user_id: i32,
+    pub xp: i64,
+    pub level: i64,
+    pub last_message_at: String,
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct LevelEntryModel {
+    pub user_id: i32,
+    pub discord_id: i64,
+    pub xp: i64,
+    pub level: i64,
+    pub position: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, FromRow)]
+pub struct GamblingLimitsModel {
+    pub user_id: i32,
+    pub daily_limit: Option<i64>,
+    pub weekly_limit: Option<i64>,
+    pub pending_daily_limit: Option<i64>,
+    pub pending_daily_at: Option<String>,
+    pub pending_weekly_limit: Option<i64>,
+    pub pending_weekly_at: Option<String>,
+    pub excluded_until: Option<String>,
+}
+
+impl GamblingLimitsModel {
+    pub fn excluded_until_datetime(&self) -> Option<DateTime<Utc>> {
+        self.excluded_until
+            .as_deref()
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct GameSessionModel {
+    pub id: i32,
+    pub game: String,
+    pub user_id: i32,
+    pub discord_id: i64,
+    pub channel_id: Option<i64>,
+    pub message_id: Option<i64>,
+    pub wager: i64,
+    pub wager_transaction_id: i32,
+    pub layout: String,
+    pub revealed: String,
+    pub status: String,
+    pub created_at: String,
+}
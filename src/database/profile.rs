@@ -0,0 +1,21 @@
+use super::models::GameStatsModel;
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+/// Lifetime mines figures derived from the ledger. Refunded rounds are not counted
+/// as played, and their refunds cancel the wager in the net result
+pub async fn game_stats(pool: &SqlitePool, user_id: i32) -> Result<GameStatsModel, SqlxError> {
+    sqlx::query_as::<_, GameStatsModel>(
+        "SELECT \
+            COALESCE(SUM(CASE WHEN kind = 'mines_wager' THEN 1 END), 0) \
+                - COALESCE(SUM(CASE WHEN kind = 'mines_refund' THEN 1 END), 0) AS mines_played, \
+            COALESCE(MAX(CASE WHEN kind IN ('mines_cashout', 'mines_autocashout') \
+                THEN amount END), 0) AS biggest_cashout, \
+            COALESCE(SUM(amount), 0) AS mines_net \
+        FROM currency_transactions \
+        WHERE user_id = ? AND currency = 'dollars' \
+        AND kind IN ('mines_wager', 'mines_refund', 'mines_cashout', 'mines_autocashout')",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+}
@@ -1,4 +1,7 @@
-use super::models::RewardStateModel;
+use super::{
+    models::{RewardStateModel, UserModel},
+    wallet::{self, WalletChange},
+};
 use chrono::{DateTime, Utc};
 use sqlx::{Error as SqlxError, sqlite::SqlitePool};
 
@@ -9,7 +12,7 @@ pub async fn get(
     reward_type: &str,
 ) -> Result<Option<RewardStateModel>, SqlxError> {
     sqlx::query_as::<_, RewardStateModel>(
-        "SELECT id, user_id, reward_type, last_claimed_at, next_reset_at, total_claims
+        "SELECT id, user_id, reward_type, last_claimed_at, next_reset_at, total_claims, streak
          FROM reward_states WHERE user_id = ? AND reward_type = ?",
     )
     .bind(user_id)
@@ -21,7 +24,7 @@ pub async fn get(
 /// Gets all reward states for a user
 pub async fn get_all(pool: &SqlitePool, user_id: i32) -> Result<Vec<RewardStateModel>, SqlxError> {
     sqlx::query_as::<_, RewardStateModel>(
-        "SELECT id, user_id, reward_type, last_claimed_at, next_reset_at, total_claims
+        "SELECT id, user_id, reward_type, last_claimed_at, next_reset_at, total_claims, streak
          FROM reward_states WHERE user_id = ?",
     )
     .bind(user_id)
@@ -29,35 +32,186 @@ pub async fn get_all(pool: &SqlitePool, user_id: i32) -> Result<Vec<RewardStateM
     .await
 }
 
-/// Updates or creates a reward state
-pub async fn upsert(
+/// A reward claim and the balance changes it pays out
+pub struct RewardClaim<'a> {
+    pub reward_type: &'a str,
+    pub claimed_at: DateTime<Utc>,
+    pub next_reset_at: DateTime<Utc>,
+    pub streak: i64,
+    pub payouts: Vec<WalletChange>,
+}
+
+/// Claims a reward in a single conditional write: the state only moves forward when the
+/// reward is available (`next_reset_at <= claimed_at`), and the payouts are applied in the
+/// same transaction. Returns `None` when another claim already took this period
+pub async fn claim(
     pool: &SqlitePool,
     user_id: i32,
-    reward_type: &str,
-    last_claimed_at: Option<DateTime<Utc>>,
-    next_reset_at: Option<DateTime<Utc>>,
-    total_claims: i64,
-) -> Result<RewardStateModel, SqlxError> {
-    let last_claimed_str = last_claimed_at.map(|dt| dt.to_rfc3339());
-    let next_reset_str = next_reset_at.map(|dt| dt.to_rfc3339());
-
-    sqlx::query(
-        "INSERT INTO reward_states (user_id, reward_type, last_claimed_at, next_reset_at, total_claims)
-         VALUES (?, ?, ?, ?, ?)
+    claim: RewardClaim<'_>,
+) -> Result<Option<(RewardStateModel, UserModel)>, SqlxError> {
+    let mut tx = pool.begin().await?;
+
+    let Some(state) = sqlx::query_as::<_, RewardStateModel>(
+        "INSERT INTO reward_states (user_id, reward_type, last_claimed_at, next_reset_at, total_claims, streak)
+         VALUES (?, ?, ?, ?, 1, ?)
          ON CONFLICT(user_id, reward_type) DO UPDATE SET
             last_claimed_at = excluded.last_claimed_at,
             next_reset_at = excluded.next_reset_at,
-            total_claims = excluded.total_claims",
+            total_claims = reward_states.total_claims + 1,
+            streak = excluded.streak
+         WHERE reward_states.next_reset_at IS NULL
+            OR julianday(reward_states.next_reset_at) <= julianday(excluded.last_claimed_at)
+         RETURNING id, user_id, reward_type, last_claimed_at, next_reset_at, total_claims, streak",
     )
     .bind(user_id)
-    .bind(reward_type)
-    .bind(&last_claimed_str)
-    .bind(&next_reset_str)
-    .bind(total_claims)
-    .execute(pool)
+    .bind(claim.reward_type)
+    .bind(claim.claimed_at.to_rfc3339())
+    .bind(claim.next_reset_at.to_rfc3339())
+    .bind(claim.streak)
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let mut user = sqlx::query_as::<_, UserModel>(
+        "SELECT id, discord_id, dollars, diamonds, created_at FROM users WHERE id = ?",
+    )
+    .bind(user_id)
+    .fetch_one(&mut *tx)
     .await?;
+    for change in &claim.payouts {
+        let Some(update) = wallet::apply_in(&mut tx, user_id, change).await? else {
+            return Err(SqlxError::RowNotFound);
+        };
+        user = update.user;
+    }
+
+    tx.commit().await?;
+    Ok(Some((state, user)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{Currency, user};
+    use chrono::Duration;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use tokio::task::JoinSet;
+
+    const CLAIMERS: usize = 8;
+
+    /// In-memory database shared by several connections, so spawned claims really race.
+    /// Each test passes its own `name` to get a database of its own
+    async fn memory_pool(name: &str) -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(CLAIMERS as u32)
+            .connect(&format!("sqlite:file:{name}?mode=memory&cache=shared"))
+            .await
+            .expect("in-memory database");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        pool
+    }
+
+    fn daily_claim(claimed_at: DateTime<Utc>, amount: i64) -> RewardClaim<'static> {
+        RewardClaim {
+            reward_type: "daily",
+            claimed_at,
+            next_reset_at: claimed_at + Duration::days(1),
+            streak: 1,
+            payouts: vec![WalletChange::new(Currency::Dollars, amount, "reward_claim")],
+        }
+    }
+
+    async fn ledger_claims(pool: &SqlitePool, user_id: i32) -> i64 {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM currency_transactions WHERE user_id = ? AND kind = 'reward_claim'",
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .expect("ledger count")
+    }
+
+    #[tokio::test]
+    async fn concurrent_claims_pay_out_once() {
+        let pool = memory_pool("concurrent_claims_pay_out_once").await;
+        let user = user::get_or_create(&pool, 1).await.unwrap();
+        let now = Utc::now();
+
+        let mut claims = JoinSet::new();
+        for _ in 0..CLAIMERS {
+            let pool = pool.clone();
+            claims.spawn(async move { claim(&pool, user.id, daily_claim(now, 100)).await });
+        }
+
+        let mut winners = 0;
+        while let Some(result) = claims.join_next().await {
+            if result.unwrap().unwrap().is_some() {
+                winners += 1;
+            }
+        }
+
+        let state = get(&pool, user.id, "daily").await.unwrap().unwrap();
+        let user = user::get_or_create(&pool, 1).await.unwrap();
+        assert_eq!(winners, 1);
+        assert_eq!(state.total_claims, 1);
+        assert_eq!(user.dollars, 100);
+        assert_eq!(ledger_claims(&pool, user.id).await, 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_claims_after_reset_pay_out_once_more() {
+        let pool = memory_pool("concurrent_claims_after_reset_pay_out_once_more").await;
+        let user = user::get_or_create(&pool, 1).await.unwrap();
+        let first = Utc::now() - Duration::days(2);
+        claim(&pool, user.id, daily_claim(first, 100))
+            .await
+            .unwrap()
+            .expect("first claim");
+
+        let now = Utc::now();
+        let mut claims = JoinSet::new();
+        for _ in 0..CLAIMERS {
+            let pool = pool.clone();
+            claims.spawn(async move { claim(&pool, user.id, daily_claim(now, 50)).await });
+        }
+
+        let mut winners = 0;
+        while let Some(result) = claims.join_next().await {
+            if result.unwrap().unwrap().is_some() {
+                winners += 1;
+            }
+        }
+
+        let state = get(&pool, user.id, "daily").await.unwrap().unwrap();
+        let user = user::get_or_create(&pool, 1).await.unwrap();
+        assert_eq!(winners, 1);
+        assert_eq!(state.total_claims, 2);
+        assert_eq!(user.dollars, 150);
+        assert_eq!(ledger_claims(&pool, user.id).await, 2);
+    }
+
+    #[tokio::test]
+    async fn claim_before_reset_is_rejected() {
+        let pool = memory_pool("claim_before_reset_is_rejected").await;
+        let user = user::get_or_create(&pool, 1).await.unwrap();
+        let now = Utc::now();
+        claim(&pool, user.id, daily_claim(now, 100))
+            .await
+            .unwrap()
+            .expect("first claim");
+
+        let retry = claim(&pool, user.id, daily_claim(now + Duration::hours(1), 100))
+            .await
+            .unwrap();
 
-    get(pool, user_id, reward_type)
-        .await?
-        .ok_or(SqlxError::RowNotFound)
+        let user = user::get_or_create(&pool, 1).await.unwrap();
+        assert!(retry.is_none());
+        assert_eq!(user.dollars, 100);
+        assert_eq!(ledger_claims(&pool, user.id).await, 1);
+    }
 }
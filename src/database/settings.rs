@@ -0,0 +1,38 @@
+use super::models::UserSettingsModel;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+/// Gets a user's settings, falling back to the defaults when none were saved
+pub async fn get(pool: &SqlitePool, user_id: i32) -> Result<UserSettingsModel, SqlxError> {
+    let settings = sqlx::query_as::<_, UserSettingsModel>(
+        "SELECT user_id, hide_balance FROM user_settings WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(settings.unwrap_or(UserSettingsModel {
+        user_id,
+        hide_balance: false,
+    }))
+}
+
+/// Sets whether the user's balance is hidden from other people
+pub async fn set_hide_balance(
+    pool: &SqlitePool,
+    user_id: i32,
+    hide_balance: bool,
+) -> Result<UserSettingsModel, SqlxError> {
+    sqlx::query_as::<_, UserSettingsModel>(
+        "INSERT INTO user_settings (user_id, hide_balance, updated_at) VALUES (?, ?, ?) \
+        ON CONFLICT(user_id) DO UPDATE SET \
+            hide_balance = excluded.hide_balance, \
+            updated_at = excluded.updated_at \
+        RETURNING user_id, hide_balance",
+    )
+    .bind(user_id)
+    .bind(hide_balance)
+    .bind(Utc::now().to_rfc3339())
+    .fetch_one(pool)
+    .await
+}
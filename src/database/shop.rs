@@ -0,0 +1,128 @@
+use super::{
+    models::{ShopItemModel, UserModel},
+    wallet::{self, Currency, WalletChange},
+};
+use chrono::Utc;
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+const ITEM_COLUMNS: &str = "id, slug, name, description, price, currency, kind";
+
+/// How ownership of an item behaves
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ItemKind {
+    /// Stacks; can be bought repeatedly and used up by other features
+    Consumable,
+    /// Owned at most once
+    Cosmetic,
+}
+
+impl ItemKind {
+    pub fn from_db_name(name: &str) -> Option<Self> {
+        match name {
+            "consumable" => Some(Self::Consumable),
+            "cosmetic" => Some(Self::Cosmetic),
+            _ => None,
+        }
+    }
+
+    pub fn is_stackable(self) -> bool {
+        matches!(self, Self::Consumable)
+    }
+}
+
+impl ShopItemModel {
+    pub fn item_kind(&self) -> Option<ItemKind> {
+        ItemKind::from_db_name(&self.kind)
+    }
+
+    pub fn price_currency(&self) -> Option<Currency> {
+        Currency::from_db_name(&self.currency)
+    }
+}
+
+/// Lists the items currently for sale, cheapest first within each currency
+pub async fn list_active(pool: &SqlitePool) -> Result<Vec<ShopItemModel>, SqlxError> {
+    sqlx::query_as::<_, ShopItemModel>(&format!(
+        "SELECT {ITEM_COLUMNS} FROM shop_items WHERE active = 1 \
+        ORDER BY currency DESC, price ASC, id ASC"
+    ))
+    .fetch_all(pool)
+    .await
+}
+
+/// Finds an item that is for sale by its slug
+pub async fn find_active_by_slug(
+    pool: &SqlitePool,
+    slug: &str,
+) -> Result<Option<ShopItemModel>, SqlxError> {
+    sqlx::query_as::<_, ShopItemModel>(&format!(
+        "SELECT {ITEM_COLUMNS} FROM shop_items WHERE active = 1 AND slug = ?"
+    ))
+    .bind(slug)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Result of a purchase attempt
+pub enum PurchaseOutcome {
+    Completed { user: UserModel, owned: i64 },
+    InsufficientFunds,
+    AlreadyOwned,
+}
+
+/// Debits `quantity` units of an item through the ledger (`shop_purchase`) and adds them
+/// to the buyer's inventory, all in one transaction
+pub async fn purchase(
+    pool: &SqlitePool,
+    user_id: i32,
+    item: &ShopItemModel,
+    quantity: i64,
+) -> Result<PurchaseOutcome, SqlxError> {
+    let (Some(kind), Some(currency)) = (item.item_kind(), item.price_currency()) else {
+        return Err(SqlxError::Decode(
+            format!("invalid kind or currency on shop item {}", item.slug).into(),
+        ));
+    };
+
+    let mut tx = pool.begin().await?;
+
+    let owned = sqlx::query_scalar::<_, i64>(
+        "SELECT COALESCE(SUM(quantity), 0) FROM inventory WHERE user_id = ? AND item_id = ?",
+    )
+    .bind(user_id)
+    .bind(item.id)
+    .fetch_one(&mut *tx)
+    .await?;
+    if !kind.is_stackable() && owned > 0 {
+        return Ok(PurchaseOutcome::AlreadyOwned);
+    }
+
+    let change = WalletChange::new(currency, -(item.price * quantity), "shop_purchase")
+        .with_context(format!("shop:{}:{}", item.slug, quantity));
+    let Some(update) = wallet::apply_in(&mut tx, user_id, &change).await? else {
+        return Ok(PurchaseOutcome::InsufficientFunds);
+    };
+
+    let now = Utc::now().to_rfc3339();
+    let owned = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO inventory (user_id, item_id, quantity, acquired_at, updated_at) \
+        VALUES (?, ?, ?, ?, ?) \
+        ON CONFLICT(user_id, item_id) DO UPDATE SET \
+            quantity = quantity + excluded.quantity, \
+            updated_at = excluded.updated_at \
+        RETURNING quantity",
+    )
+    .bind(user_id)
+    .bind(item.id)
+    .bind(quantity)
+    .bind(&now)
+    .bind(&now)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(PurchaseOutcome::Completed {
+        user: update.user,
+        owned,
+    })
+}
@@ -0,0 +1,44 @@
+use super::models::{EconomySupplyModel, EconomyWindowModel};
+use chrono::{DateTime, Utc};
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+/// Aggregates ledger activity with `since <= created_at < until`.
+/// Mines refunds are netted against wagers, since they undo a wager that never played out
+pub async fn window_summary(
+    pool: &SqlitePool,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<EconomyWindowModel, SqlxError> {
+    sqlx::query_as::<_, EconomyWindowModel>(
+        "SELECT \
+            COALESCE(SUM(CASE WHEN kind = 'reward_claim' AND currency = 'dollars' \
+                THEN amount END), 0) AS minted_dollars, \
+            COALESCE(SUM(CASE WHEN kind = 'reward_claim' AND currency = 'diamonds' \
+                THEN amount END), 0) AS minted_diamonds, \
+            COALESCE(-SUM(CASE WHEN kind IN ('mines_wager', 'mines_refund') \
+                THEN amount END), 0) AS mines_wagered, \
+            COALESCE(SUM(CASE WHEN kind IN ('mines_cashout', 'mines_autocashout') \
+                THEN amount END), 0) AS mines_paid_out, \
+            COALESCE(SUM(CASE WHEN currency = 'dollars' THEN amount END), 0) AS net_dollars, \
+            COALESCE(SUM(CASE WHEN currency = 'diamonds' THEN amount END), 0) AS net_diamonds, \
+            COUNT(DISTINCT user_id) AS active_users \
+        FROM currency_transactions \
+        WHERE julianday(created_at) >= julianday(?) AND julianday(created_at) < julianday(?)",
+    )
+    .bind(since.to_rfc3339())
+    .bind(until.to_rfc3339())
+    .fetch_one(pool)
+    .await
+}
+
+/// Current money supply held in wallets
+pub async fn supply(pool: &SqlitePool) -> Result<EconomySupplyModel, SqlxError> {
+    sqlx::query_as::<_, EconomySupplyModel>(
+        "SELECT COUNT(*) AS users, \
+        COALESCE(SUM(dollars), 0) AS dollars, \
+        COALESCE(SUM(diamonds), 0) AS diamonds \
+        FROM users",
+    )
+    .fetch_one(pool)
+    .await
+}
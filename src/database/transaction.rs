@@ -1,71 +1,346 @@
-use super::models::CurrencyTransactionModel;
-use chrono::Utc;
-use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+use super::models::{CurrencyTransactionModel, KindEntryModel, TransactionTotalsModel};
+use chrono::{DateTime, Utc};
+use sqlx::{Error as SqlxError, QueryBuilder, Sqlite, SqliteConnection, sqlite::SqlitePool};
 
-/// Inserts a new entry into the currency transaction ledger
+const TRANSACTION_COLUMNS: &str = "id, user_id, amount, balance_after, currency, kind, context, \
+    created_at, counterpart_id, reversal_of, actor_id";
+
+/// Column values for a new ledger entry
+pub struct NewTransaction<'a> {
+    pub user_id: i32,
+    pub amount: i64,
+    pub balance_after: i64,
+    pub currency: &'a str,
+    pub kind: &'a str,
+    pub context: Option<&'a str>,
+    pub reversal_of: Option<i32>,
+    pub actor_id: Option<i64>,
+}
+
+/// Restricts entries to credits or debits
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransactionSign {
+    Gains,
+    Losses,
+}
+
+/// Optional criteria narrowing a user's ledger entries.
+/// `since` is inclusive and `until` exclusive
+#[derive(Clone, Debug, Default)]
+pub struct TransactionFilter {
+    pub currency: Option<String>,
+    pub kind: Option<String>,
+    pub sign: Option<TransactionSign>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl TransactionFilter {
+    fn push_conditions(&self, builder: &mut QueryBuilder<'_, Sqlite>, user_id: i32) {
+        builder.push(" WHERE user_id = ").push_bind(user_id);
+        if let Some(currency) = &self.currency {
+            builder.push(" AND currency = ").push_bind(currency.clone());
+        }
+        match self.sign {
+            Some(TransactionSign::Gains) => {
+                builder.push(" AND amount > 0");
+            }
+            Some(TransactionSign::Losses) => {
+                builder.push(" AND amount < 0");
+            }
+            None => {}
+        }
+        if let Some(kind) = &self.kind {
+            builder.push(" AND kind = ").push_bind(kind.clone());
+        }
+        if let Some(since) = self.since {
+            builder
+                .push(" AND julianday(created_at) >= julianday(")
+                .push_bind(since.to_rfc3339())
+                .push(")");
+        }
+        if let Some(until) = self.until {
+            builder
+                .push(" AND julianday(created_at) < julianday(")
+                .push_bind(until.to_rfc3339())
+                .push(")");
+        }
+    }
+}
+
+/// Inserts a new entry into the currency transaction ledger.
+/// Takes a connection so it can share the caller's transaction, see `database::wallet`
 pub async fn insert(
-    pool: &SqlitePool,
-    user_id: i32,
-    amount: i64,
-    balance_after: i64,
-    currency: &str,
-    kind: &str,
-    context: Option<String>,
+    conn: &mut SqliteConnection,
+    entry: &NewTransaction<'_>,
 ) -> Result<CurrencyTransactionModel, SqlxError> {
     let created_at = Utc::now().to_rfc3339();
-    let context_ref = context.as_deref();
 
-    let result = sqlx::query(
+    sqlx::query_as::<_, CurrencyTransactionModel>(&format!(
         "INSERT INTO currency_transactions \
-        (user_id, amount, balance_after, currency, kind, context, created_at) \
-        VALUES (?, ?, ?, ?, ?, ?, ?)",
-    )
-    .bind(user_id)
-    .bind(amount)
-    .bind(balance_after)
-    .bind(currency)
-    .bind(kind)
-    .bind(context_ref)
+        (user_id, amount, balance_after, currency, kind, context, created_at, reversal_of, \
+        actor_id) \
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?) \
+        RETURNING {TRANSACTION_COLUMNS}"
+    ))
+    .bind(entry.user_id)
+    .bind(entry.amount)
+    .bind(entry.balance_after)
+    .bind(entry.currency)
+    .bind(entry.kind)
+    .bind(entry.context)
     .bind(&created_at)
-    .execute(pool)
+    .bind(entry.reversal_of)
+    .bind(entry.actor_id)
+    .fetch_one(conn)
+    .await
+}
+
+/// Finds a single ledger entry by id
+pub async fn find_by_id(
+    conn: &mut SqliteConnection,
+    transaction_id: i32,
+) -> Result<Option<CurrencyTransactionModel>, SqlxError> {
+    sqlx::query_as::<_, CurrencyTransactionModel>(&format!(
+        "SELECT {TRANSACTION_COLUMNS} FROM currency_transactions WHERE id = ?"
+    ))
+    .bind(transaction_id)
+    .fetch_optional(conn)
+    .await
+}
+
+/// Points two ledger entries at each other, e.g. both sides of a transfer
+pub async fn link_counterparts(
+    conn: &mut SqliteConnection,
+    first_id: i32,
+    second_id: i32,
+) -> Result<(), SqlxError> {
+    sqlx::query(
+        "UPDATE currency_transactions \
+        SET counterpart_id = CASE id WHEN ? THEN ? ELSE ? END \
+        WHERE id IN (?, ?)",
+    )
+    .bind(first_id)
+    .bind(second_id)
+    .bind(first_id)
+    .bind(first_id)
+    .bind(second_id)
+    .execute(conn)
     .await?;
+    Ok(())
+}
 
-    let id = result.last_insert_rowid() as i32;
+/// Returns `balance_after` of a user's most recent entry in the given currency
+pub async fn latest_balance(
+    conn: &mut SqliteConnection,
+    user_id: i32,
+    currency: &str,
+) -> Result<Option<i64>, SqlxError> {
+    sqlx::query_scalar::<_, i64>(
+        "SELECT balance_after FROM currency_transactions \
+        WHERE user_id = ? AND currency = ? \
+        ORDER BY id DESC LIMIT 1",
+    )
+    .bind(user_id)
+    .bind(currency)
+    .fetch_optional(conn)
+    .await
+}
 
-    sqlx::query_as::<_, CurrencyTransactionModel>(
-        "SELECT id, user_id, amount, balance_after, currency, kind, context, created_at \
-        FROM currency_transactions WHERE id = ?",
+/// Sums the amounts of a user's entries of a given kind and currency created since `since`
+pub async fn sum_kind_since(
+    conn: &mut SqliteConnection,
+    user_id: i32,
+    currency: &str,
+    kind: &str,
+    since: DateTime<Utc>,
+) -> Result<i64, SqlxError> {
+    sqlx::query_scalar::<_, i64>(
+        "SELECT COALESCE(SUM(amount), 0) FROM currency_transactions \
+        WHERE user_id = ? AND currency = ? AND kind = ? \
+        AND julianday(created_at) >= julianday(?)",
     )
-    .bind(id)
-    .fetch_one(pool)
+    .bind(user_id)
+    .bind(currency)
+    .bind(kind)
+    .bind(since.to_rfc3339())
+    .fetch_one(conn)
     .await
 }
 
-/// Returns the most recent transactions for a user, ordered from newest to oldest
-pub async fn list_recent_by_user(
+/// Returns up to `limit` matching entries older than `before_id` (or the newest ones when `None`),
+/// newest first
+pub async fn list_before(
     pool: &SqlitePool,
     user_id: i32,
+    filter: &TransactionFilter,
+    before_id: Option<i32>,
     limit: i64,
 ) -> Result<Vec<CurrencyTransactionModel>, SqlxError> {
-    let capped_limit = limit.clamp(1, 200);
-    sqlx::query_as::<_, CurrencyTransactionModel>(
-        "SELECT id, user_id, amount, balance_after, currency, kind, context, created_at \
-        FROM currency_transactions \
-        WHERE user_id = ? \
-        ORDER BY id DESC \
+    let mut builder = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT {TRANSACTION_COLUMNS} FROM currency_transactions"
+    ));
+    filter.push_conditions(&mut builder, user_id);
+    if let Some(before_id) = before_id {
+        builder.push(" AND id < ").push_bind(before_id);
+    }
+    builder.push(" ORDER BY id DESC LIMIT ").push_bind(limit);
+
+    builder
+        .build_query_as::<CurrencyTransactionModel>()
+        .fetch_all(pool)
+        .await
+}
+
+/// Returns up to `limit` matching entries with an id greater than `after_id`, oldest first.
+/// Callers page through the full history by passing the last id they received
+pub async fn list_after(
+    pool: &SqlitePool,
+    user_id: i32,
+    filter: &TransactionFilter,
+    after_id: i32,
+    limit: i64,
+) -> Result<Vec<CurrencyTransactionModel>, SqlxError> {
+    let mut builder = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT {TRANSACTION_COLUMNS} FROM currency_transactions"
+    ));
+    filter.push_conditions(&mut builder, user_id);
+    builder
+        .push(" AND id > ")
+        .push_bind(after_id)
+        .push(" ORDER BY id ASC LIMIT ")
+        .push_bind(limit);
+
+    builder
+        .build_query_as::<CurrencyTransactionModel>()
+        .fetch_all(pool)
+        .await
+}
+
+/// Counts matching entries and sums their amounts, grouped by currency
+pub async fn totals_by_currency(
+    pool: &SqlitePool,
+    user_id: i32,
+    filter: &TransactionFilter,
+) -> Result<Vec<TransactionTotalsModel>, SqlxError> {
+    let mut builder = QueryBuilder::<Sqlite>::new(
+        "SELECT currency, COUNT(*) AS count, COALESCE(SUM(amount), 0) AS net \
+        FROM currency_transactions",
+    );
+    filter.push_conditions(&mut builder, user_id);
+    builder.push(" GROUP BY currency ORDER BY currency");
+
+    builder
+        .build_query_as::<TransactionTotalsModel>()
+        .fetch_all(pool)
+        .await
+}
+
+/// Lists the distinct entry kinds starting with `prefix`, used for autocomplete
+pub async fn list_kinds(
+    pool: &SqlitePool,
+    prefix: &str,
+    limit: i64,
+) -> Result<Vec<String>, SqlxError> {
+    sqlx::query_scalar::<_, String>(
+        "SELECT DISTINCT kind FROM currency_transactions \
+        WHERE kind LIKE ? || '%' ESCAPE '\\' \
+        ORDER BY kind LIMIT ?",
+    )
+    .bind(escape_like(prefix))
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Lists entries of a kind across all users, newest first, optionally for a single user
+pub async fn list_recent_by_kind(
+    pool: &SqlitePool,
+    kind: &str,
+    user_id: Option<i32>,
+    limit: i64,
+) -> Result<Vec<KindEntryModel>, SqlxError> {
+    sqlx::query_as::<_, KindEntryModel>(
+        "SELECT t.id, u.discord_id, t.amount, t.balance_after, t.currency, t.context, \
+        t.actor_id, t.created_at \
+        FROM currency_transactions t \
+        JOIN users u ON u.id = t.user_id \
+        WHERE t.kind = ? AND (? IS NULL OR t.user_id = ?) \
+        ORDER BY t.id DESC \
         LIMIT ?",
     )
+    .bind(kind)
+    .bind(user_id)
     .bind(user_id)
-    .bind(capped_limit)
+    .bind(limit)
     .fetch_all(pool)
     .await
 }
 
-/// Removes a specific transaction entry, used when rolling back pending operations
-pub async fn delete_by_id(pool: &SqlitePool, transaction_id: i32) -> Result<(), SqlxError> {
-    sqlx::query("DELETE FROM currency_transactions WHERE id = ?")
-        .bind(transaction_id)
-        .execute(pool)
-        .await?;
-    Ok(())
+/// Checks whether an entry has already been reversed
+pub async fn is_reversed(
+    conn: &mut SqliteConnection,
+    transaction_id: i32,
+) -> Result<bool, SqlxError> {
+    sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM currency_transactions WHERE reversal_of = ?)",
+    )
+    .bind(transaction_id)
+    .fetch_one(conn)
+    .await
+}
+
+/// Loads the entries with the given ids, in no particular order
+pub async fn list_by_ids(
+    pool: &SqlitePool,
+    ids: &[i32],
+) -> Result<Vec<CurrencyTransactionModel>, SqlxError> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut builder = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT {TRANSACTION_COLUMNS} FROM currency_transactions WHERE id IN ("
+    ));
+    let mut separated = builder.separated(", ");
+    for id in ids {
+        separated.push_bind(*id);
+    }
+    builder.push(")");
+
+    builder
+        .build_query_as::<CurrencyTransactionModel>()
+        .fetch_all(pool)
+        .await
+}
+
+/// Loads the entries that reverse any of the given ids
+pub async fn list_reversals_of(
+    pool: &SqlitePool,
+    ids: &[i32],
+) -> Result<Vec<CurrencyTransactionModel>, SqlxError> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut builder = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT {TRANSACTION_COLUMNS} FROM currency_transactions WHERE reversal_of IN ("
+    ));
+    let mut separated = builder.separated(", ");
+    for id in ids {
+        separated.push_bind(*id);
+    }
+    builder.push(")");
+
+    builder
+        .build_query_as::<CurrencyTransactionModel>()
+        .fetch_all(pool)
+        .await
 }
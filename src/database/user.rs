@@ -45,25 +45,3 @@ pub async fn get_or_create(pool: &SqlitePool, discord_id: i64) -> Result<UserMod
         create(pool, discord_id).await
     }
 }
-
-/// Updates user's balance
-pub async fn update_balance(
-    pool: &SqlitePool,
-    user_id: i32,
-    dollars: i64,
-    diamonds: i64,
-) -> Result<UserModel, SqlxError> {
-    sqlx::query("UPDATE users SET dollars = ?, diamonds = ? WHERE id = ?")
-        .bind(dollars)
-        .bind(diamonds)
-        .bind(user_id)
-        .execute(pool)
-        .await?;
-
-    sqlx::query_as::<_, UserModel>(
-        "SELECT id, discord_id, dollars, diamonds, created_at FROM users WHERE id = ?",
-    )
-    .bind(user_id)
-    .fetch_one(pool)
-    .await
-}
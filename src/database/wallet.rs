@@ -0,0 +1,271 @@
+use super::{
+    models::{CurrencyTransactionModel, UserModel},
+    transaction::{self, NewTransaction},
+};
+use chrono::{DateTime, Utc};
+use sqlx::{Error as SqlxError, SqliteConnection, sqlite::SqlitePool};
+
+/// Currencies stored on the `users` table that can be moved through the wallet
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Currency {
+    Dollars,
+    Diamonds,
+}
+
+impl Currency {
+    pub fn db_name(self) -> &'static str {
+        match self {
+            Self::Dollars => "dollars",
+            Self::Diamonds => "diamonds",
+        }
+    }
+
+    pub fn from_db_name(name: &str) -> Option<Self> {
+        match name {
+            "dollars" => Some(Self::Dollars),
+            "diamonds" => Some(Self::Diamonds),
+            _ => None,
+        }
+    }
+
+    pub fn balance_of(self, user: &UserModel) -> i64 {
+        match self {
+            Self::Dollars => user.dollars,
+            Self::Diamonds => user.diamonds,
+        }
+    }
+
+    /// Guarded delta update: only touches the row when the resulting balance stays non-negative
+    fn update_sql(self) -> &'static str {
+        match self {
+            Self::Dollars => {
+                "UPDATE users SET dollars = dollars + ? \
+                WHERE id = ? AND dollars + ? >= 0 \
+                RETURNING id, discord_id, dollars, diamonds, created_at"
+            }
+            Self::Diamonds => {
+                "UPDATE users SET diamonds = diamonds + ? \
+                WHERE id = ? AND diamonds + ? >= 0 \
+                RETURNING id, discord_id, dollars, diamonds, created_at"
+            }
+        }
+    }
+}
+
+/// A balance delta together with the ledger metadata describing it
+pub struct WalletChange {
+    pub currency: Currency,
+    pub amount: i64,
+    pub kind: &'static str,
+    pub context: Option<String>,
+    pub reversal_of: Option<i32>,
+    pub actor_id: Option<i64>,
+}
+
+impl WalletChange {
+    pub fn new(currency: Currency, amount: i64, kind: &'static str) -> Self {
+        Self {
+            currency,
+            amount,
+            kind,
+            context: None,
+            reversal_of: None,
+            actor_id: None,
+        }
+    }
+
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// Ledger row describing this change once applied
+    pub fn entry(&self, user_id: i32, balance_after: i64) -> NewTransaction<'_> {
+        NewTransaction {
+            user_id,
+            amount: self.amount,
+            balance_after,
+            currency: self.currency.db_name(),
+            kind: self.kind,
+            context: self.context.as_deref(),
+            reversal_of: self.reversal_of,
+            actor_id: self.actor_id,
+        }
+    }
+
+    /// Records the Discord ID of whoever triggered the change on someone else's behalf
+    pub fn with_actor(mut self, actor_id: i64) -> Self {
+        self.actor_id = Some(actor_id);
+        self
+    }
+}
+
+/// Updated user row and the ledger entry written for a successful change
+pub struct WalletUpdate {
+    pub user: UserModel,
+    pub transaction: CurrencyTransactionModel,
+}
+
+/// Applies a change in its own transaction.
+/// Returns `None` (and writes nothing) when the balance would go negative
+pub async fn apply(
+    pool: &SqlitePool,
+    user_id: i32,
+    change: WalletChange,
+) -> Result<Option<WalletUpdate>, SqlxError> {
+    let mut tx = pool.begin().await?;
+    let update = apply_in(&mut tx, user_id, &change).await?;
+    if update.is_some() {
+        tx.commit().await?;
+    }
+    Ok(update)
+}
+
+/// Applies a change on an already open transaction so several mutations can be committed together
+pub async fn apply_in(
+    conn: &mut SqliteConnection,
+    user_id: i32,
+    change: &WalletChange,
+) -> Result<Option<WalletUpdate>, SqlxError> {
+    let Some(user) = sqlx::query_as::<_, UserModel>(change.currency.update_sql())
+        .bind(change.amount)
+        .bind(user_id)
+        .bind(change.amount)
+        .fetch_optional(&mut *conn)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let entry = change.entry(user.id, change.currency.balance_of(&user));
+    let transaction = transaction::insert(&mut *conn, &entry).await?;
+
+    Ok(Some(WalletUpdate { user, transaction }))
+}
+
+/// Moves a balance to exactly `target` by applying the difference as a single change.
+/// `change.amount` is replaced by that difference; returns `None` when the balance
+/// already matches or `target` is negative
+pub async fn set_balance(
+    pool: &SqlitePool,
+    user_id: i32,
+    target: i64,
+    mut change: WalletChange,
+) -> Result<Option<WalletUpdate>, SqlxError> {
+    if target < 0 {
+        return Ok(None);
+    }
+
+    let mut tx = pool.begin().await?;
+    let current = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT {} FROM users WHERE id = ?",
+        change.currency.db_name()
+    ))
+    .bind(user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    change.amount = target - current;
+    if change.amount == 0 {
+        return Ok(None);
+    }
+
+    let update = apply_in(&mut tx, user_id, &change).await?;
+    if update.is_some() {
+        tx.commit().await?;
+    }
+    Ok(update)
+}
+
+/// Result of a peer-to-peer transfer attempt
+pub enum TransferOutcome {
+    Completed {
+        sender: UserModel,
+        outgoing_id: i32,
+        incoming_id: i32,
+    },
+    InsufficientFunds,
+    LimitExceeded {
+        remaining: i64,
+    },
+}
+
+/// Moves `amount` from one user to another in a single transaction, writing paired
+/// `transfer_out`/`transfer_in` entries that reference each other.
+/// `daily_limit` caps how much the sender may send since `window_start`
+pub async fn transfer(
+    pool: &SqlitePool,
+    sender: &UserModel,
+    recipient: &UserModel,
+    currency: Currency,
+    amount: i64,
+    daily_limit: i64,
+    window_start: DateTime<Utc>,
+) -> Result<TransferOutcome, SqlxError> {
+    // Take the write lock before reading the daily total: a deferred transaction would fail
+    // with SQLITE_BUSY when upgrading if a concurrent transfer wrote after our read
+    let mut tx = pool.begin_with("BEGIN IMMEDIATE").await?;
+
+    let sent_today = -transaction::sum_kind_since(
+        &mut tx,
+        sender.id,
+        currency.db_name(),
+        "transfer_out",
+        window_start,
+    )
+    .await?;
+    if sent_today + amount > daily_limit {
+        return Ok(TransferOutcome::LimitExceeded {
+            remaining: (daily_limit - sent_today).max(0),
+        });
+    }
+
+    let outgoing = WalletChange::new(currency, -amount, "transfer_out")
+        .with_context(format!("transfer:{}", recipient.discord_id));
+    let Some(outgoing) = apply_in(&mut tx, sender.id, &outgoing).await? else {
+        return Ok(TransferOutcome::InsufficientFunds);
+    };
+
+    let incoming = WalletChange::new(currency, amount, "transfer_in")
+        .with_context(format!("transfer:{}", sender.discord_id));
+    let Some(incoming) = apply_in(&mut tx, recipient.id, &incoming).await? else {
+        return Err(SqlxError::RowNotFound);
+    };
+
+    transaction::link_counterparts(&mut tx, outgoing.transaction.id, incoming.transaction.id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(TransferOutcome::Completed {
+        sender: outgoing.user,
+        outgoing_id: outgoing.transaction.id,
+        incoming_id: incoming.transaction.id,
+    })
+}
+
+/// Undoes a ledger entry by appending a compensating entry of the opposite amount
+/// that points back at it; the original row is kept. Runs on an already open transaction.
+/// Returns `None` when the entry does not belong to the user, was already reversed,
+/// or the balance can no longer cover it
+pub async fn revert_in(
+    conn: &mut SqliteConnection,
+    user_id: i32,
+    transaction_id: i32,
+    kind: &'static str,
+    context: impl Into<String>,
+) -> Result<Option<WalletUpdate>, SqlxError> {
+    let Some(entry) = transaction::find_by_id(&mut *conn, transaction_id).await? else {
+        return Ok(None);
+    };
+    if entry.user_id != user_id || transaction::is_reversed(&mut *conn, transaction_id).await? {
+        return Ok(None);
+    }
+
+    let Some(currency) = Currency::from_db_name(&entry.currency) else {
+        return Ok(None);
+    };
+
+    let mut change = WalletChange::new(currency, -entry.amount, kind).with_context(context);
+    change.reversal_of = Some(entry.id);
+    apply_in(conn, user_id, &change).await
+}
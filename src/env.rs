@@ -1,4 +1,7 @@
 pub const DEFAULT_DATABASE_URL: &str = "sqlite://fumo.db";
+pub const DEFAULT_TRANSFER_LIMIT_DOLLARS: i64 = 50_000;
+pub const DEFAULT_TRANSFER_LIMIT_DIAMONDS: i64 = 50;
+pub const DEFAULT_JOKENPO_HOUSE_CUT_PERCENT: i64 = 5;
 
 type EnvError = Box<dyn std::error::Error + Send + Sync>;
 type EnvResult<T> = Result<T, EnvError>;
@@ -37,3 +40,46 @@ pub fn owner_ids() -> EnvResult<Vec<u64>> {
         Err(e) => Err(Box::new(e) as EnvError),
     }
 }
+
+/// Tunable economy settings loaded once at startup
+#[derive(Clone, Copy, Debug)]
+pub struct EconomyConfig {
+    pub transfer_limit_dollars: i64,
+    pub transfer_limit_diamonds: i64,
+    /// Percentage of a wagered jokenpo pot kept by the house
+    pub jokenpo_house_cut_percent: i64,
+}
+
+/// Reads the economy settings, falling back to defaults for missing values
+pub fn economy_config() -> EnvResult<EconomyConfig> {
+    let jokenpo_house_cut_percent = optional_i64(
+        "FUMO_JOKENPO_HOUSE_CUT_PERCENT",
+        DEFAULT_JOKENPO_HOUSE_CUT_PERCENT,
+    )?;
+    if !(0..=100).contains(&jokenpo_house_cut_percent) {
+        return Err("FUMO_JOKENPO_HOUSE_CUT_PERCENT must be between 0 and 100".into());
+    }
+
+    Ok(EconomyConfig {
+        transfer_limit_dollars: optional_i64(
+            "FUMO_TRANSFER_LIMIT_DOLLARS",
+            DEFAULT_TRANSFER_LIMIT_DOLLARS,
+        )?,
+        transfer_limit_diamonds: optional_i64(
+            "FUMO_TRANSFER_LIMIT_DIAMONDS",
+            DEFAULT_TRANSFER_LIMIT_DIAMONDS,
+        )?,
+        jokenpo_house_cut_percent,
+    })
+}
+
+fn optional_i64(key: &str, default: i64) -> EnvResult<i64> {
+    match dotenvy::var(key) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|err| Box::new(err) as EnvError),
+        Err(dotenvy::Error::EnvVar(std::env::VarError::NotPresent)) => Ok(default),
+        Err(e) => Err(Box::new(e) as EnvError),
+    }
+}
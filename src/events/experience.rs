@@ -0,0 +1,95 @@
+use crate::{
+    Data, Error,
+    constants::icon,
+    database::{self, Currency, WalletChange},
+    fumo::DatabaseContainer,
+    functions::{
+        format::{discord::bold, format_currency, pretty_message},
+        levels::{self, MESSAGE_COOLDOWN_SECS, MESSAGE_XP},
+    },
+};
+use chrono::Utc;
+use poise::{
+    self, BoxFuture,
+    serenity_prelude::{self as serenity, Mentionable},
+};
+use rand::Rng;
+
+pub fn event_handler<'a>(
+    framework: poise::FrameworkContext<'a, Data, Error>,
+    event: &'a serenity::FullEvent,
+) -> BoxFuture<'a, Result<(), Error>> {
+    Box::pin(async move { handle_message(framework, event).await })
+}
+
+async fn handle_message(
+    framework: poise::FrameworkContext<'_, Data, Error>,
+    event: &serenity::FullEvent,
+) -> Result<(), Error> {
+    let serenity::FullEvent::Message { new_message } = event else {
+        return Ok(());
+    };
+
+    if new_message.author.bot {
+        return Ok(());
+    }
+    let Some(guild_id) = new_message.guild_id else {
+        return Ok(());
+    };
+
+    let db = {
+        let data = framework.serenity_context.data.read().await;
+        match data.get::<DatabaseContainer>() {
+            Some(db) => db.clone(),
+            None => return Ok(()),
+        }
+    };
+
+    let guild_id = guild_id.get() as i64;
+    let user = database::get_or_create_user(&db, new_message.author.id.get() as i64).await?;
+    let amount = rand::rng().random_range(MESSAGE_XP.0..=MESSAGE_XP.1);
+    let Some(member) = database::level::add_xp(
+        &db,
+        guild_id,
+        user.id,
+        amount,
+        Utc::now(),
+        MESSAGE_COOLDOWN_SECS,
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let new_level = levels::progress_for(member.xp).level;
+    if new_level <= member.level {
+        return Ok(());
+    }
+
+    let reward = levels::level_up_reward(member.level, new_level);
+    let change = (reward > 0).then(|| {
+        WalletChange::new(Currency::Dollars, reward, "level_up_reward")
+            .with_context(format!("level:{guild_id}:{new_level}"))
+    });
+    if !database::level::level_up(&db, guild_id, user.id, member.level, new_level, change).await? {
+        return Ok(());
+    }
+
+    new_message
+        .channel_id
+        .say(
+            framework.serenity_context,
+            pretty_message(
+                icon::GIFT,
+                format!(
+                    "{} subiu para o nível {} e ganhou {} moedas!",
+                    new_message.author.mention(),
+                    bold(new_level.to_string()),
+                    bold(format_currency(reward))
+                ),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::{Data, Error};
 use poise::{self, BoxFuture, serenity_prelude as serenity};
 
+pub mod experience;
 pub mod mention;
 
 pub type EventHandler = for<'a> fn(
@@ -10,7 +11,7 @@ pub type EventHandler = for<'a> fn(
 
 /// Returns the list of registered event handlers
 pub fn load_all() -> &'static [EventHandler] {
-    &[mention::event_handler]
+    &[mention::event_handler, experience::event_handler]
 }
 
 /// Dispatches the incoming event to every registered handler in order
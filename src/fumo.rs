@@ -1,4 +1,4 @@
-use crate::{commands, events, functions};
+use crate::{commands, env::EconomyConfig, events, functions};
 use poise::serenity_prelude as serenity;
 use serenity::prelude::TypeMapKey;
 use sqlx::SqlitePool;
@@ -25,6 +25,7 @@ pub fn prefix_options() -> poise::PrefixFrameworkOptions<Data, Error> {
 pub struct Data {
     pub shard_manager: Arc<serenity::ShardManager>,
     pub database: SqlitePool,
+    pub economy: EconomyConfig,
 }
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -36,17 +37,25 @@ impl TypeMapKey for ShardManagerContainer {
     type Value = Arc<serenity::ShardManager>;
 }
 
+/// Exposes the pool to event handlers, which can run before `Data` is built
+pub struct DatabaseContainer;
+
+impl TypeMapKey for DatabaseContainer {
+    type Value = SqlitePool;
+}
+
 /// Builds the Poise framework with all commands and the provided prefix options
 pub fn build_framework(
     prefix_options: poise::PrefixFrameworkOptions<Data, Error>,
     database: SqlitePool,
     owner_ids: Vec<u64>,
+    economy: EconomyConfig,
 ) -> poise::Framework<Data, Error> {
     poise::Framework::builder()
         .options(framework_options(prefix_options, owner_ids))
         .setup(move |ctx, ready, framework| {
             let database = database.clone();
-            Box::pin(async move { setup_framework(ctx, ready, framework, database).await })
+            Box::pin(async move { setup_framework(ctx, ready, framework, database, economy).await })
         })
         .build()
 }
@@ -83,6 +92,9 @@ fn framework_options(
         command_check: Some(|ctx| {
             Box::pin(async move { functions::bot::blacklist::enforce_global_blacklist(ctx).await })
         }),
+        pre_command: |ctx| {
+            Box::pin(async move { functions::bot::membership::track_guild_member(ctx).await })
+        },
         event_handler: events::dispatch,
         prefix_options,
         owners,
@@ -96,16 +108,27 @@ async fn setup_framework(
     ready: &serenity::Ready,
     framework: &poise::Framework<Data, Error>,
     database: SqlitePool,
+    economy: EconomyConfig,
 ) -> Result<Data, Error> {
     register_commands(ctx, framework).await?;
+    ctx.data
+        .write()
+        .await
+        .insert::<DatabaseContainer>(database.clone());
     let shard_manager = extract_shard_manager(ctx).await;
     // TODO: re-enable automatic avatar rotation on startup when the feature is stable
     // functions::bot::avatar::spawn_avatar_rotation_task(ctx.http.clone());
+    functions::bot::game_recovery::recover_game_sessions(ctx, &database).await;
+    functions::bot::bank_interest::spawn_interest_task(
+        database.clone(),
+        commands::economy::RESET_CONFIG,
+    );
     println!("{} is connected and ready", ready.user.display_name());
 
     Ok(Data {
         shard_manager,
         database,
+        economy,
     })
 }
 
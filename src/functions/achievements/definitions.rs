@@ -0,0 +1,91 @@
+use super::AchievementEvent;
+use crate::database::Currency;
+
+/// A badge and the event condition that unlocks it
+pub struct Achievement {
+    pub slug: &'static str,
+    pub badge: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub reward: Option<(Currency, i64)>,
+    unlocked_by: fn(&AchievementEvent) -> bool,
+}
+
+impl Achievement {
+    pub fn is_unlocked_by(&self, event: &AchievementEvent) -> bool {
+        (self.unlocked_by)(event)
+    }
+}
+
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        slug: "mines_first_cashout",
+        badge: "💎",
+        name: "Primeiro resgate",
+        description: "Resgate uma rodada de Mines.",
+        reward: Some((Currency::Dollars, 500)),
+        unlocked_by: |event| matches!(event, AchievementEvent::MinesCashout { .. }),
+    },
+    Achievement {
+        slug: "mines_max_multiplier",
+        badge: "💣",
+        name: "Nervos de aço",
+        description: "Chegue ao auto-resgate do Mines com o multiplicador máximo.",
+        reward: Some((Currency::Diamonds, 20)),
+        unlocked_by: |event| {
+            matches!(
+                event,
+                AchievementEvent::MinesCashout {
+                    max_multiplier: true
+                }
+            )
+        },
+    },
+    Achievement {
+        slug: "memory_perfect",
+        badge: "🧠",
+        name: "Memória fotográfica",
+        description: "Vença um jogo da memória sem errar nenhum par.",
+        reward: Some((Currency::Diamonds, 10)),
+        unlocked_by: |event| matches!(event, AchievementEvent::MemoryWon { mismatches: 0, .. }),
+    },
+    Achievement {
+        slug: "memory_versus_win",
+        badge: "🤝",
+        name: "Mente afiada",
+        description: "Vença um desafio de memória contra outra pessoa.",
+        reward: Some((Currency::Dollars, 300)),
+        unlocked_by: |event| matches!(event, AchievementEvent::MemoryWon { versus: true, .. }),
+    },
+    Achievement {
+        slug: "race_win",
+        badge: "🏁",
+        name: "Pé de coelho",
+        description: "Vença uma corrida de animais.",
+        reward: Some((Currency::Dollars, 300)),
+        unlocked_by: |event| matches!(event, AchievementEvent::RaceWon),
+    },
+    Achievement {
+        slug: "daily_streak_7",
+        badge: "📅",
+        name: "Frequentador",
+        description: "Colete a recompensa diária 7 vezes seguidas.",
+        reward: None,
+        unlocked_by: |event| daily_streak_at_least(event, 7),
+    },
+    Achievement {
+        slug: "daily_streak_30",
+        badge: "🔥",
+        name: "Devoto",
+        description: "Colete a recompensa diária 30 vezes seguidas.",
+        reward: Some((Currency::Diamonds, 50)),
+        unlocked_by: |event| daily_streak_at_least(event, 30),
+    },
+];
+
+fn daily_streak_at_least(event: &AchievementEvent, target: i64) -> bool {
+    matches!(
+        event,
+        AchievementEvent::RewardStreak { reward_type: "daily", streak } if *streak >= target
+    )
+}
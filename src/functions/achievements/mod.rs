@@ -0,0 +1,92 @@
+use crate::{
+    constants::icon,
+    database::{self, Currency, WalletChange},
+    functions::format::{discord::bold, format_currency, pretty_message},
+};
+use sqlx::{Error as SqlxError, sqlite::SqlitePool};
+
+mod definitions;
+
+pub use definitions::{ACHIEVEMENTS, Achievement};
+
+/// Something that happened in a game or in the economy that may unlock achievements
+pub enum AchievementEvent {
+    /// `max_multiplier` is set when the round reached the auto-cashout, where the multiplier peaks
+    MinesCashout {
+        max_multiplier: bool,
+    },
+    MemoryWon {
+        mismatches: u32,
+        versus: bool,
+    },
+    RaceWon,
+    RewardStreak {
+        reward_type: &'static str,
+        streak: i64,
+    },
+}
+
+/// Matches an event against every definition and unlocks the ones the user did not have yet,
+/// paying their rewards as `achievement_reward` entries. Returns the newly unlocked achievements
+pub async fn record(
+    db: &SqlitePool,
+    discord_id: i64,
+    event: AchievementEvent,
+) -> Result<Vec<&'static Achievement>, SqlxError> {
+    let candidates: Vec<&Achievement> = ACHIEVEMENTS
+        .iter()
+        .filter(|achievement| achievement.is_unlocked_by(&event))
+        .collect();
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let user = database::get_or_create_user(db, discord_id).await?;
+    let mut unlocked = Vec::new();
+    for achievement in candidates {
+        let reward = achievement.reward.map(|(currency, amount)| {
+            WalletChange::new(currency, amount, "achievement_reward")
+                .with_context(format!("achievement:{}", achievement.slug))
+        });
+        if database::achievement::unlock(db, user.id, achievement.slug, reward).await? {
+            unlocked.push(achievement);
+        }
+    }
+
+    Ok(unlocked)
+}
+
+/// One line per unlocked achievement, for announcing inside a game's embed
+pub fn format_unlocks(unlocked: &[&Achievement]) -> Option<String> {
+    if unlocked.is_empty() {
+        return None;
+    }
+
+    Some(
+        unlocked
+            .iter()
+            .map(|achievement| {
+                let reward = achievement
+                    .reward
+                    .map(|(currency, amount)| format!(" • +{}", format_reward(currency, amount)))
+                    .unwrap_or_default();
+                pretty_message(
+                    icon::GIFT,
+                    format!(
+                        "Conquista desbloqueada: {} {}{reward}",
+                        achievement.badge,
+                        bold(achievement.name)
+                    ),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+pub fn format_reward(currency: Currency, amount: i64) -> String {
+    match currency {
+        Currency::Dollars => format!("{} moedas", format_currency(amount)),
+        Currency::Diamonds => format!("{} diamantes", format_currency(amount)),
+    }
+}
@@ -0,0 +1,232 @@
+use crate::database::{self, BankAccountModel};
+use crate::functions::time::{self, ResetPeriod, ResetTime};
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::SqlitePool;
+use std::time::Duration;
+
+const INTEREST_CHECK_INTERVAL_SECS: u64 = 10 * 60; // 10 minutes
+
+/// Savings bracket with its daily rate (in basis points) and the most it can pay per day
+pub struct InterestTier {
+    pub name: &'static str,
+    pub min_balance: i64,
+    pub rate_bps: i64,
+    pub daily_cap: i64,
+}
+
+impl InterestTier {
+    pub fn interest_for(&self, balance: i64) -> i64 {
+        (balance.max(0) * self.rate_bps / 10_000).min(self.daily_cap)
+    }
+}
+
+/// Ordered from the highest balance down
+pub const INTEREST_TIERS: [InterestTier; 3] = [
+    InterestTier {
+        name: "Ouro",
+        min_balance: 100_000,
+        rate_bps: 25,
+        daily_cap: 1_000,
+    },
+    InterestTier {
+        name: "Prata",
+        min_balance: 10_000,
+        rate_bps: 50,
+        daily_cap: 300,
+    },
+    InterestTier {
+        name: "Bronze",
+        min_balance: 0,
+        rate_bps: 100,
+        daily_cap: 50,
+    },
+];
+
+pub fn tier_for(balance: i64) -> &'static InterestTier {
+    INTEREST_TIERS
+        .iter()
+        .find(|tier| balance >= tier.min_balance)
+        .unwrap_or(&INTEREST_TIERS[INTEREST_TIERS.len() - 1])
+}
+
+/// Spawns a background task that pays savings interest for every daily reset that has passed.
+/// Runs once right away so resets missed while the bot was offline are caught up on startup
+pub fn spawn_interest_task(database: SqlitePool, reset_config: ResetTime) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(INTEREST_CHECK_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+            if let Err(err) = pay_due_interest(&database, &reset_config).await {
+                eprintln!("Failed to pay bank interest: {err}");
+            }
+        }
+    });
+}
+
+async fn pay_due_interest(
+    database: &SqlitePool,
+    reset_config: &ResetTime,
+) -> Result<(), sqlx::Error> {
+    let latest_reset = time::last_daily_reset(Utc::now(), reset_config);
+    for account in database::bank::list_due(database, latest_reset).await? {
+        catch_up_account(database, account, latest_reset, reset_config).await?;
+    }
+    Ok(())
+}
+
+/// Pays each missed reset in order. Each day earns on the lowest balance it saw, so money
+/// parked in savings just before the reset (or taken out right after it, before the ticker
+/// runs) earns nothing. See [`database::bank::earning_balance`] for how earlier interest counts
+async fn catch_up_account(
+    database: &SqlitePool,
+    mut account: BankAccountModel,
+    latest_reset: DateTime<Utc>,
+    reset_config: &ResetTime,
+) -> Result<(), sqlx::Error> {
+    let Some(mut paid_until) = account.last_interest_datetime() else {
+        eprintln!(
+            "Bank account {} has an invalid last_interest_at: {}",
+            account.id, account.last_interest_at
+        );
+        return Ok(());
+    };
+
+    loop {
+        let next_reset = time::next_reset_from(paid_until, ResetPeriod::Daily, reset_config);
+        if next_reset > latest_reset {
+            return Ok(());
+        }
+
+        let balance =
+            database::bank::earning_balance(database, account.user_id, paid_until, next_reset)
+                .await?;
+        let interest = tier_for(balance).interest_for(balance);
+        match database::bank::pay_interest(database, &account, next_reset, interest).await? {
+            Some(updated) => {
+                account = updated;
+                paid_until = next_reset;
+            }
+            // Another run already paid this reset
+            None => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{Currency, WalletChange, bank::BankOutcome, user, wallet};
+    use chrono::Duration as ChronoDuration;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    const DAYS: usize = 4;
+    const SAVINGS: i64 = 50_000;
+
+    async fn memory_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("in-memory database");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        pool
+    }
+
+    /// Ledger entries are stamped with the current time; tests move them to when they
+    /// would have been written
+    async fn backdate_ledger_since(
+        pool: &SqlitePool,
+        user_id: i32,
+        after_id: i32,
+        at: DateTime<Utc>,
+    ) {
+        sqlx::query("UPDATE currency_transactions SET created_at = ? WHERE user_id = ? AND id > ?")
+            .bind(at.to_rfc3339())
+            .bind(user_id)
+            .bind(after_id)
+            .execute(pool)
+            .await
+            .expect("backdate ledger");
+    }
+
+    async fn last_ledger_id(pool: &SqlitePool) -> i32 {
+        sqlx::query_scalar("SELECT COALESCE(MAX(id), 0) FROM currency_transactions")
+            .fetch_one(pool)
+            .await
+            .expect("last ledger id")
+    }
+
+    /// Opens a savings account at `opened_at` holding `SAVINGS`, deposited an hour before it
+    async fn saver(pool: &SqlitePool, discord_id: i64, opened_at: DateTime<Utc>) -> i32 {
+        let user = user::get_or_create(pool, discord_id).await.unwrap();
+        let before = last_ledger_id(pool).await;
+        wallet::apply(
+            pool,
+            user.id,
+            WalletChange::new(Currency::Dollars, SAVINGS, "test"),
+        )
+        .await
+        .unwrap()
+        .expect("funded wallet");
+        let outcome = database::bank::deposit(pool, user.id, SAVINGS, opened_at)
+            .await
+            .unwrap();
+        assert!(matches!(outcome, BankOutcome::Completed { .. }));
+        backdate_ledger_since(pool, user.id, before, opened_at - ChronoDuration::hours(1)).await;
+        user.id
+    }
+
+    async fn savings_of(
+        pool: &SqlitePool,
+        user_id: i32,
+        opened_at: DateTime<Utc>,
+    ) -> BankAccountModel {
+        database::bank::get_or_open(pool, user_id, opened_at)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn catching_up_pays_the_same_as_live_payouts() {
+        let pool = memory_pool().await;
+        let reset_config = ResetTime::brt(0, 0);
+        let opened_at = time::last_daily_reset(
+            Utc::now() - ChronoDuration::days(DAYS as i64 + 2),
+            &reset_config,
+        );
+        let resets: Vec<_> = (1..=DAYS)
+            .scan(opened_at, |reset, _| {
+                *reset = time::next_reset_from(*reset, ResetPeriod::Daily, &reset_config);
+                Some(*reset)
+            })
+            .collect();
+
+        // The ticker pays each reset a few minutes after it happens
+        let live = saver(&pool, 1, opened_at).await;
+        for reset in &resets {
+            let before = last_ledger_id(&pool).await;
+            let account = savings_of(&pool, live, opened_at).await;
+            catch_up_account(&pool, account, *reset, &reset_config)
+                .await
+                .unwrap();
+            backdate_ledger_since(&pool, live, before, *reset + ChronoDuration::minutes(5)).await;
+        }
+
+        // The bot was offline for every reset and pays them all at once
+        let offline = saver(&pool, 2, opened_at).await;
+        let account = savings_of(&pool, offline, opened_at).await;
+        catch_up_account(&pool, account, resets[DAYS - 1], &reset_config)
+            .await
+            .unwrap();
+
+        let live = savings_of(&pool, live, opened_at).await;
+        let offline = savings_of(&pool, offline, opened_at).await;
+        assert_eq!(live.balance, offline.balance);
+        // Interest compounds: later days earn on earlier interest
+        let first_day = tier_for(SAVINGS).interest_for(SAVINGS);
+        assert!(live.balance > SAVINGS + first_day * DAYS as i64);
+    }
+}
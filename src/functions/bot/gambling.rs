@@ -0,0 +1,176 @@
+use crate::{
+    Context, Error,
+    constants::{colors, icon},
+    database::{self, GamblingLimitsModel, gambling::LimitPeriod, loan::LoanStatus},
+    functions::{
+        format::{discord::bold, format_currency, pretty_message},
+        session::{self, SessionGuard},
+        time,
+    },
+};
+use chrono::{DateTime, Duration, Utc};
+use poise::serenity_prelude as serenity;
+use sqlx::sqlite::SqlitePool;
+
+/// How long a raised or removed limit waits before taking effect
+pub const COOLING_OFF: Duration = Duration::hours(24);
+
+/// Rolling window each limit period looks back over
+pub fn window(period: LimitPeriod) -> Duration {
+    match period {
+        LimitPeriod::Daily => Duration::days(1),
+        LimitPeriod::Weekly => Duration::days(7),
+    }
+}
+
+/// Why a wager is not allowed
+pub enum WagerBlock {
+    LoanDefaulted,
+    Excluded(DateTime<Utc>),
+    OverLimit {
+        period: LimitPeriod,
+        limit: i64,
+        lost: i64,
+    },
+}
+
+/// Checks a defaulted loan, self-exclusion and loss limits before a wager of `amount`.
+/// Returns the first reason that blocks it, if any
+pub async fn check_wager(
+    db: &SqlitePool,
+    user_id: i32,
+    amount: i64,
+) -> Result<Option<WagerBlock>, Error> {
+    let now = Utc::now();
+    let open_loan = database::loan::find_open(db, user_id, now).await?;
+    if open_loan.is_some_and(|loan| loan.status() == Some(LoanStatus::Defaulted)) {
+        return Ok(Some(WagerBlock::LoanDefaulted));
+    }
+
+    let limits = database::gambling::get(db, user_id, now).await?;
+    if let Some(until) = limits.excluded_until_datetime()
+        && until > now
+    {
+        return Ok(Some(WagerBlock::Excluded(until)));
+    }
+
+    for period in [LimitPeriod::Daily, LimitPeriod::Weekly] {
+        let Some(limit) = period.current(&limits) else {
+            continue;
+        };
+
+        let lost = database::gambling::net_loss_since(db, user_id, now - window(period)).await?;
+        if lost + amount > limit {
+            return Ok(Some(WagerBlock::OverLimit {
+                period,
+                limit,
+                lost,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Runs [`check_wager`] for the command author, replying to them when the wager is not allowed.
+/// Every betting command should call this before taking the stake
+pub async fn ensure_can_wager(ctx: &Context<'_>, user_id: i32, amount: i64) -> Result<bool, Error> {
+    let db = ctx.data().database.clone();
+    let message = match check_wager(&db, user_id, amount).await? {
+        None => return Ok(true),
+        Some(WagerBlock::Excluded(until)) => {
+            send_exclusion_notice(ctx, until).await?;
+            return Ok(false);
+        }
+        Some(WagerBlock::LoanDefaulted) => pretty_message(
+            icon::ERROR,
+            "Seu empréstimo venceu. Quite a dívida com `/empréstimo pagar` para voltar a apostar.",
+        ),
+        Some(WagerBlock::OverLimit {
+            period,
+            limit,
+            lost,
+        }) => {
+            let label = match period {
+                LimitPeriod::Daily => "diário",
+                LimitPeriod::Weekly => "semanal",
+            };
+            pretty_message(
+                icon::ERROR,
+                format!(
+                    "Essa aposta ultrapassa seu limite {label} de perdas ({} moedas). \
+                    Você ainda pode arriscar {} moedas.",
+                    bold(format_currency(limit)),
+                    bold(format_currency((limit - lost).max(0)))
+                ),
+            )
+        }
+    };
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(message)
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(false)
+}
+
+/// Claims the author's wagering session, replying when another game of theirs is still open.
+/// Keep the guard alive until the stake is settled; dropping it frees the user to bet again
+pub async fn claim_wager_session(
+    ctx: &Context<'_>,
+) -> Result<Option<SessionGuard<serenity::UserId>>, Error> {
+    let guard = session::wager_sessions().claim(ctx.author().id);
+    if guard.is_none() {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(pretty_message(
+                    icon::ERROR,
+                    "Você já tem uma aposta em andamento. Termine-a antes de começar outra.",
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+    }
+    Ok(guard)
+}
+
+/// Whether the user is currently self-excluded
+pub fn is_excluded(limits: &GamblingLimitsModel, now: DateTime<Utc>) -> bool {
+    limits
+        .excluded_until_datetime()
+        .is_some_and(|until| until > now)
+}
+
+async fn send_exclusion_notice(ctx: &Context<'_>, until: DateTime<Utc>) -> Result<(), Error> {
+    let description = [
+        pretty_message(
+            icon::ERROR,
+            "Você pediu uma pausa nas apostas e não pode apostar por enquanto.",
+        ),
+        pretty_message(
+            icon::TIMER,
+            format!(
+                "A autoexclusão termina {} ({})",
+                time::describe_relative(until),
+                time::describe_absolute(until)
+            ),
+        ),
+        String::new(),
+        pretty_message(
+            icon::PLUS,
+            "A pausa não pode ser encurtada. Os outros comandos continuam liberados.",
+        ),
+    ];
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("{} Autoexclusão ativa", icon::ERROR))
+        .description(description.join("\n"))
+        .colour(colors::MOON);
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
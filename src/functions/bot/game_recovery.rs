@@ -0,0 +1,121 @@
+use crate::{
+    commands::{jokenpo, mines},
+    constants::icon,
+    database::{self, GameSessionModel},
+    functions::format::{discord::bold, format_currency, pretty_message},
+};
+use poise::serenity_prelude::{self as serenity, Mentionable};
+use serenity::builder::{CreateMessage, EditMessage};
+use sqlx::sqlite::SqlitePool;
+
+/// A wagered game whose open sessions are refunded on startup
+struct RecoverableGame {
+    name: &'static str,
+    label: &'static str,
+    refund_kind: &'static str,
+}
+
+const RECOVERABLE_GAMES: &[RecoverableGame] = &[
+    RecoverableGame {
+        name: mines::GAME_NAME,
+        label: "rodada de Mines",
+        refund_kind: "mines_refund",
+    },
+    RecoverableGame {
+        name: jokenpo::GAME_NAME,
+        label: "partida de JoKenPo",
+        refund_kind: "jokenpo_refund",
+    },
+    RecoverableGame {
+        name: jokenpo::tournament::GAME_NAME,
+        label: "inscrição no torneio de JoKenPo",
+        refund_kind: "jokenpo_tournament_refund",
+    },
+];
+
+/// Settles wagered games left open by a restart: the wager is refunded through the ledger,
+/// the original message loses its buttons and the player is told in the channel (or by DM
+/// when the message was never sent)
+pub async fn recover_game_sessions(ctx: &serenity::Context, database: &SqlitePool) {
+    for game in RECOVERABLE_GAMES {
+        recover_game(ctx, database, game).await;
+    }
+}
+
+async fn recover_game(ctx: &serenity::Context, database: &SqlitePool, game: &RecoverableGame) {
+    let sessions = match database::game_session::list_active(database, game.name).await {
+        Ok(sessions) => sessions,
+        Err(err) => {
+            eprintln!("Failed to load interrupted {} sessions: {err:?}", game.name);
+            return;
+        }
+    };
+
+    for session in sessions {
+        let refunded = database::game_session::close_with_refund(
+            database,
+            session.id,
+            "refunded",
+            game.refund_kind,
+            "Reembolso automático: partida interrompida",
+        )
+        .await;
+
+        match refunded {
+            Ok(Some(_)) => notify_player(ctx, &session, game).await,
+            Ok(None) => {}
+            Err(err) => eprintln!(
+                "Failed to refund {} session {}: {err:?}",
+                game.name, session.id
+            ),
+        }
+    }
+}
+
+async fn notify_player(
+    ctx: &serenity::Context,
+    session: &GameSessionModel,
+    game: &RecoverableGame,
+) {
+    let player = serenity::UserId::new(session.discord_id as u64);
+    let notice = pretty_message(
+        icon::GIFT,
+        format!(
+            "{}, sua {} foi interrompida por uma reinicialização. \
+            Devolvi {} moedas para a sua carteira.",
+            player.mention(),
+            game.label,
+            bold(format_currency(session.wager))
+        ),
+    );
+
+    let result = match (session.channel_id, session.message_id) {
+        (Some(channel_id), Some(message_id)) => {
+            let channel_id = serenity::ChannelId::new(channel_id as u64);
+            let message_id = serenity::MessageId::new(message_id as u64);
+            let _ = channel_id
+                .edit_message(ctx, message_id, EditMessage::new().components(Vec::new()))
+                .await;
+            channel_id
+                .send_message(
+                    ctx,
+                    CreateMessage::new()
+                        .content(notice)
+                        .reference_message((channel_id, message_id)),
+                )
+                .await
+                .map(|_| ())
+        }
+        _ => player
+            .direct_message(ctx, CreateMessage::new().content(notice))
+            .await
+            .map(|_| ()),
+    };
+
+    if let Err(err) = result {
+        eprintln!(
+            "Failed to notify player about {} session {}: {err:?}",
+            game.name, session.id
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{Context, database};
+use poise::serenity_prelude as serenity;
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+/// A membership already recorded this recently is not written again
+const MEMBERSHIP_REFRESH: Duration = Duration::from_secs(60 * 60);
+/// Past this many entries, stale ones are dropped before adding another
+const SEEN_PRUNE_THRESHOLD: usize = 10_000;
+
+type MemberKey = (serenity::UserId, serenity::GuildId);
+
+static RECENTLY_SEEN: OnceLock<Mutex<HashMap<MemberKey, Instant>>> = OnceLock::new();
+
+/// Pre-command hook that records the guild the author was seen in, used by per-guild rankings.
+/// Each member is written at most once per `MEMBERSHIP_REFRESH`, so most commands skip the database
+pub async fn track_guild_member(ctx: Context<'_>) {
+    let Some(guild_id) = ctx.guild_id() else {
+        return;
+    };
+    if ctx.author().bot {
+        return;
+    }
+
+    let key = (ctx.author().id, guild_id);
+    if !mark_seen(key) {
+        return;
+    }
+
+    let db = ctx.data().database.clone();
+    let discord_id = ctx.author().id.get() as i64;
+    let result = match database::get_or_create_user(&db, discord_id).await {
+        Ok(user) => database::record_guild_member(&db, user.id, guild_id.get() as i64).await,
+        Err(err) => Err(err),
+    };
+
+    if let Err(err) = result {
+        eprintln!("Failed to record guild membership: {err:?}");
+        // Let the next command try again
+        seen()
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .remove(&key);
+    }
+}
+
+fn seen() -> &'static Mutex<HashMap<MemberKey, Instant>> {
+    RECENTLY_SEEN.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Marks `key` as seen now. Returns `false` when it was already seen within `MEMBERSHIP_REFRESH`
+fn mark_seen(key: MemberKey) -> bool {
+    let mut seen = seen().lock().unwrap_or_else(|err| err.into_inner());
+    let now = Instant::now();
+    if seen
+        .get(&key)
+        .is_some_and(|seen_at| now.duration_since(*seen_at) < MEMBERSHIP_REFRESH)
+    {
+        return false;
+    }
+
+    if seen.len() >= SEEN_PRUNE_THRESHOLD {
+        seen.retain(|_, seen_at| now.duration_since(*seen_at) < MEMBERSHIP_REFRESH);
+    }
+    seen.insert(key, now);
+    true
+}
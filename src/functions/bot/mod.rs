@@ -1,2 +1,6 @@
 pub mod avatar;
+pub mod bank_interest;
 pub mod blacklist;
+pub mod gambling;
+pub mod game_recovery;
+pub mod membership;
@@ -1,3 +1,4 @@
+use std::future::{self, Future};
 use std::time::Duration;
 
 use poise::serenity_prelude as serenity;
@@ -19,6 +20,31 @@ pub async fn paginate<F>(
 ) -> Result<(), Error>
 where
     F: FnMut(usize, usize) -> (serenity::CreateEmbed, Vec<CreateActionRow>),
+{
+    paginate_async(
+        ctx,
+        total_pages,
+        timeout,
+        ephemeral,
+        initial_page,
+        |page, total_pages| future::ready(Ok(build_page(page, total_pages))),
+    )
+    .await
+}
+
+/// Same as [`paginate`], but pages are produced asynchronously on demand,
+/// e.g. fetched from the database with a cursor when the user navigates
+pub async fn paginate_async<F, Fut>(
+    ctx: Context<'_>,
+    total_pages: usize,
+    timeout: Duration,
+    ephemeral: bool,
+    initial_page: usize,
+    mut build_page: F,
+) -> Result<(), Error>
+where
+    F: FnMut(usize, usize) -> Fut,
+    Fut: Future<Output = Result<(serenity::CreateEmbed, Vec<CreateActionRow>), Error>>,
 {
     if total_pages == 0 {
         return Ok(());
@@ -27,7 +53,7 @@ where
     let mut current_page = initial_page.min(total_pages - 1);
     let buttons = PaginationButtons::new(ctx.id());
 
-    let (embed, mut components) = build_page(current_page, total_pages);
+    let (embed, mut components) = build_page(current_page, total_pages).await?;
     components.push(build_navigation_row(&buttons, current_page, total_pages));
 
     let reply = ctx
@@ -57,7 +83,7 @@ where
         };
 
         current_page = next_index;
-        let (embed, mut components) = build_page(current_page, total_pages);
+        let (embed, mut components) = build_page(current_page, total_pages).await?;
         components.push(build_navigation_row(&buttons, current_page, total_pages));
         update_component_message(&ctx, &interaction, embed, components).await?;
     }
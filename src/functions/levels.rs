@@ -0,0 +1,37 @@
+/// XP granted per message, picked uniformly from this range
+pub const MESSAGE_XP: (i64, i64) = (15, 25);
+/// Minimum time between two messages that earn XP
+pub const MESSAGE_COOLDOWN_SECS: i64 = 60;
+const REWARD_PER_LEVEL: i64 = 100;
+
+/// XP needed to go from `level` to the next one
+pub fn xp_to_next(level: i64) -> i64 {
+    5 * level * level + 50 * level + 100
+}
+
+/// Where a total amount of XP lands on the curve
+pub struct LevelProgress {
+    pub level: i64,
+    pub current: i64,
+    pub needed: i64,
+}
+
+pub fn progress_for(total_xp: i64) -> LevelProgress {
+    let mut level = 0;
+    let mut remaining = total_xp.max(0);
+    while remaining >= xp_to_next(level) {
+        remaining -= xp_to_next(level);
+        level += 1;
+    }
+
+    LevelProgress {
+        level,
+        current: remaining,
+        needed: xp_to_next(level),
+    }
+}
+
+/// Dollars paid for reaching every level in `(from, to]`
+pub fn level_up_reward(from: i64, to: i64) -> i64 {
+    (from + 1..=to).map(|level| level * REWARD_PER_LEVEL).sum()
+}
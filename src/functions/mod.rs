@@ -1,4 +1,7 @@
+pub mod achievements;
 pub mod bot;
 pub mod format;
 pub mod interactions;
+pub mod levels;
+pub mod session;
 pub mod time;
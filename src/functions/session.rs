@@ -0,0 +1,124 @@
+use poise::serenity_prelude as serenity;
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+/// Longest a wagering session may hold its lock, comfortably above any game timeout
+const WAGER_SESSION_MAX_HOLD: Duration = Duration::from_secs(15 * 60);
+
+static WAGER_SESSIONS: OnceLock<SessionRegistry<serenity::UserId>> = OnceLock::new();
+
+/// Sessions that put a user's money at stake; a user can only have one open at a time
+pub fn wager_sessions() -> &'static SessionRegistry<serenity::UserId> {
+    WAGER_SESSIONS.get_or_init(|| SessionRegistry::new(WAGER_SESSION_MAX_HOLD))
+}
+
+/// Tracks which keys (users, channels, ...) have a session running.
+/// Claims are released when their guard drops, which also happens on panic or when the
+/// command future is cancelled. A claim held past its deadline (`max_hold` unless claimed
+/// with [`SessionRegistry::claim_for`]) is treated as abandoned
+pub struct SessionRegistry<K> {
+    /// Deadline of each active claim
+    active: Arc<Mutex<HashMap<K, Instant>>>,
+    max_hold: Duration,
+}
+
+impl<K: Copy + Eq + Hash> SessionRegistry<K> {
+    pub fn new(max_hold: Duration) -> Self {
+        Self {
+            active: Arc::new(Mutex::new(HashMap::new())),
+            max_hold,
+        }
+    }
+
+    /// Claims `key`, or returns `None` while another session still holds it
+    pub fn claim(&self, key: K) -> Option<SessionGuard<K>> {
+        self.claim_for(key, self.max_hold)
+    }
+
+    /// Like [`claim`](Self::claim), for sessions known to outlast the registry's `max_hold`
+    pub fn claim_for(&self, key: K, hold: Duration) -> Option<SessionGuard<K>> {
+        let mut active = self.active.lock().unwrap_or_else(|err| err.into_inner());
+        let now = Instant::now();
+        if active.get(&key).is_some_and(|deadline| now < *deadline) {
+            return None;
+        }
+        let deadline = now + hold;
+        active.insert(key, deadline);
+
+        Some(SessionGuard {
+            key,
+            deadline,
+            active: Arc::clone(&self.active),
+        })
+    }
+}
+
+/// Holds a claim until dropped
+pub struct SessionGuard<K: Copy + Eq + Hash> {
+    key: K,
+    deadline: Instant,
+    active: Arc<Mutex<HashMap<K, Instant>>>,
+}
+
+impl<K: Copy + Eq + Hash> Drop for SessionGuard<K> {
+    fn drop(&mut self) {
+        let mut active = self.active.lock().unwrap_or_else(|err| err.into_inner());
+        // An expired claim may have been taken over; only release our own
+        if active.get(&self.key) == Some(&self.deadline) {
+            active.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_claim_is_rejected_until_release() {
+        let registry = SessionRegistry::new(Duration::from_secs(60));
+        let guard = registry.claim(1);
+        assert!(guard.is_some());
+        assert!(registry.claim(1).is_none());
+        assert!(registry.claim(2).is_some());
+
+        drop(guard);
+        assert!(registry.claim(1).is_some());
+    }
+
+    #[test]
+    fn claim_is_released_on_panic() {
+        let registry = SessionRegistry::new(Duration::from_secs(60));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = registry.claim(1);
+            panic!("game crashed");
+        }));
+        assert!(result.is_err());
+        assert!(registry.claim(1).is_some());
+    }
+
+    #[test]
+    fn abandoned_claim_expires() {
+        let registry = SessionRegistry::new(Duration::from_secs(60));
+        let stale = registry.claim_for(1, Duration::ZERO);
+        let fresh = registry.claim(1);
+        assert!(fresh.is_some());
+
+        // The stale guard must not free the claim that replaced it
+        drop(stale);
+        assert!(registry.claim(1).is_none());
+        drop(fresh);
+        assert!(registry.claim(1).is_some());
+    }
+
+    #[test]
+    fn longer_claim_outlives_max_hold() {
+        let registry = SessionRegistry::new(Duration::ZERO);
+        let _guard = registry.claim_for(1, Duration::from_secs(60));
+        assert!(registry.claim(1).is_none());
+    }
+}
@@ -1,5 +1,6 @@
 pub mod time;
 
 pub use time::{
-    ResetTime, describe_absolute, describe_relative, describe_relative_from_str, next_reset_from,
+    ResetPeriod, ResetTime, describe_absolute, describe_relative, describe_relative_from_str,
+    last_daily_reset, next_reset_from, parse_date, start_of_day,
 };
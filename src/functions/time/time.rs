@@ -94,6 +94,29 @@ pub fn next_reset_from(
         .with_timezone(&Utc)
 }
 
+/// Returns the most recent daily reset at or before the reference date
+pub fn last_daily_reset(reference: DateTime<Utc>, reset_config: &ResetTime) -> DateTime<Utc> {
+    next_reset_from(reference, ResetPeriod::Daily, reset_config) - Duration::days(1)
+}
+
+/// Parses a user supplied date, accepting `DD/MM/AAAA` or `AAAA-MM-DD`
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    NaiveDate::parse_from_str(value, "%d/%m/%Y")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d"))
+        .ok()
+}
+
+/// Returns the instant a calendar day starts in the configured timezone
+pub fn start_of_day(date: NaiveDate, reset_config: &ResetTime) -> DateTime<Utc> {
+    reset_config
+        .timezone_offset()
+        .from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .single()
+        .expect("unique local midnight")
+        .with_timezone(&Utc)
+}
+
 /// Adds one month to a date, handling day overflow
 pub fn add_one_month(date: NaiveDate) -> NaiveDate {
     let mut year = date.year();
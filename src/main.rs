@@ -36,10 +36,11 @@ async fn main() -> Result<(), fumo::Error> {
     let intents = fumo::gateway_intents();
     let prefix_options = fumo::prefix_options();
     let owner_ids = env::owner_ids()?;
+    let economy = env::economy_config()?;
     let database = database::connect()
         .await
         .map_err(|err| -> fumo::Error { Box::new(err) })?;
 
-    let framework = fumo::build_framework(prefix_options, database, owner_ids);
+    let framework = fumo::build_framework(prefix_options, database, owner_ids, economy);
     fumo::run_client(token, intents, framework).await
 }